@@ -10,26 +10,101 @@ use fuchsia_zircon_sys as sys;
 /// Draw random bytes from the kernel's CPRNG to fill the given buffer. Returns the actual number of
 /// bytes drawn, which is always the size of the buffer provided.
 ///
+/// The underlying syscall only fills up to `ZX_CPRNG_DRAW_MAX_LEN` bytes per call, so buffers
+/// larger than that are filled with multiple calls internally.
+///
 /// Wraps the
 /// [zx_cprng_draw](https://fuchsia.googlesource.com/fuchsia/+/master/docs/zircon/syscalls/cprng_draw.md)
 /// syscall.
 pub fn cprng_draw(buffer: &mut [u8]) -> Result<usize, Status> {
-    unsafe { sys::zx_cprng_draw(buffer.as_mut_ptr(), buffer.len()) };
+    for chunk in buffer.chunks_mut(sys::ZX_CPRNG_DRAW_MAX_LEN) {
+        unsafe { sys::zx_cprng_draw(chunk.as_mut_ptr(), chunk.len()) };
+    }
     Ok(buffer.len())
 }
 
+/// Draw exactly `N` random bytes from the kernel's CPRNG.
+///
+/// Convenient for callers that want a fixed-size key, nonce, or seed rather than managing a
+/// `&mut [u8]` themselves.
+pub fn cprng_draw_array<const N: usize>() -> Result<[u8; N], Status> {
+    let mut buffer = [0; N];
+    cprng_draw(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Draw a random `u32` from the kernel's CPRNG.
+pub fn cprng_u32() -> Result<u32, Status> {
+    cprng_draw_array::<4>().map(u32::from_ne_bytes)
+}
+
+/// Draw a random `u64` from the kernel's CPRNG.
+pub fn cprng_u64() -> Result<u64, Status> {
+    cprng_draw_array::<8>().map(u64::from_ne_bytes)
+}
+
+/// Draw a random `u128` from the kernel's CPRNG.
+pub fn cprng_u128() -> Result<u128, Status> {
+    cprng_draw_array::<16>().map(u128::from_ne_bytes)
+}
+
 /// Mix the given entropy into the kernel CPRNG.
 ///
-/// The buffer must have length less than `ZX_CPRNG_ADD_ENTROPY_MAX_LEN`.
+/// Buffers longer than `ZX_CPRNG_ADD_ENTROPY_MAX_LEN` are mixed in with multiple calls
+/// internally, so arbitrarily large entropy buffers can be folded in with a single call here.
 ///
 /// Wraps the
 /// [zx_cprng_add_entropy](https://fuchsia.googlesource.com/fuchsia/+/master/docs/zircon/syscalls/cprng_add_entropy.md)
 /// syscall.
 pub fn cprng_add_entropy(buffer: &[u8]) -> Result<(), Status> {
-    let status = unsafe { sys::zx_cprng_add_entropy(buffer.as_ptr(), buffer.len()) };
-    ok(status)
+    for chunk in buffer.chunks(sys::ZX_CPRNG_ADD_ENTROPY_MAX_LEN) {
+        let status = unsafe { sys::zx_cprng_add_entropy(chunk.as_ptr(), chunk.len()) };
+        ok(status)?;
+    }
+    Ok(())
+}
+
+/// A zero-sized handle to the kernel's CPRNG.
+///
+/// Implements `rand_core::RngCore` and `rand_core::CryptoRng` on top of `cprng_draw`, so Fuchsia
+/// code can use the kernel CPRNG anywhere a `CryptoRng + RngCore` is expected, without re-gluing
+/// the syscall in every crate that needs randomness.
+#[cfg(feature = "rand")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KernelRng;
+
+#[cfg(feature = "rand")]
+impl rand_core::RngCore for KernelRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_ne_bytes(buf)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_ne_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        cprng_draw(dest).expect("zx_cprng_draw should never fail");
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        cprng_draw(dest).map(drop).map_err(|status| {
+            let code = status.into_raw() as u32;
+            rand_core::Error::from(
+                std::num::NonZeroU32::new(code)
+                    .unwrap_or_else(|| std::num::NonZeroU32::new(u32::max_value()).unwrap()),
+            )
+        })
+    }
 }
 
+#[cfg(feature = "rand")]
+impl rand_core::CryptoRng for KernelRng {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,9 +139,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cprng_typed() {
+        let array: [u8; 20] = cprng_draw_array().expect("draw should succeed");
+        assert_ne!(array, [0; 20]);
+
+        assert_ne!(cprng_u32().unwrap(), cprng_u32().unwrap());
+        assert_ne!(cprng_u64().unwrap(), cprng_u64().unwrap());
+        assert_ne!(cprng_u128().unwrap(), cprng_u128().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn kernel_rng() {
+        use rand_core::RngCore as _;
+
+        let mut rng = KernelRng::default();
+        assert_ne!(rng.next_u64(), rng.next_u64());
+
+        let mut buffer = [0; 20];
+        rng.try_fill_bytes(&mut buffer).expect("kernel cprng draw should succeed");
+        assert_ne!(buffer, [0; 20]);
+    }
+
     #[test]
     fn cprng_add() {
         let buffer = [0, 1, 2];
         assert_eq!(cprng_add_entropy(&buffer), Ok(()));
     }
+
+    #[test]
+    fn cprng_add_large() {
+        let buffer = [0; sys::ZX_CPRNG_ADD_ENTROPY_MAX_LEN + 1];
+        assert_eq!(cprng_add_entropy(&buffer), Ok(()));
+    }
 }
@@ -3,21 +3,32 @@
 // found in the LICENSE file.
 
 use {
+    aes::cipher::{generic_array::GenericArray, BlockCipher, NewBlockCipher},
+    aes::Aes128,
+    chacha20poly1305::aead::{Aead, NewAead},
+    chacha20poly1305::{ChaCha20Poly1305, Key, Nonce},
     failure::Error,
     fidl::endpoints::create_proxy,
     fidl_fuchsia_bluetooth_control::HostData,
     fidl_fuchsia_stash::{
         GetIteratorMarker, StoreAccessorMarker, StoreAccessorProxy, StoreMarker, Value,
     },
+    fuchsia_async as fasync,
     fuchsia_bluetooth::{error::Error as BtError, inspect::Inspectable, types::BondingData},
     fuchsia_inspect,
     fuchsia_syslog::{fx_log_err, fx_log_info},
+    fuchsia_zircon::{cprng_draw, Duration, Time},
+    futures::stream::StreamExt,
+    serde::{Deserialize, Serialize},
     serde_json,
+    sha2::{Digest, Sha256},
+    std::cell::RefCell,
     std::collections::HashMap,
+    std::rc::Rc,
 };
 
 #[cfg(test)]
-use {fidl::endpoints::Proxy, fuchsia_async as fasync, fuchsia_zircon as zx};
+use {fidl::endpoints::Proxy, fuchsia_zircon as zx};
 
 use crate::store::{
     keys::{
@@ -74,6 +85,315 @@ pub struct Stash {
 
     /// Handle to inspect data
     inspect: fuchsia_inspect::Node,
+
+    /// When present, bonding and host data blobs are sealed with this key before being persisted,
+    /// and are expected to be sealed when loaded back. See `StashCrypto`.
+    crypto: Option<StashCrypto>,
+
+    /// Counts of entries loaded vs. skipped as corrupt the last time this stash was initialized.
+    load_report: LoadReport,
+
+    /// Cache of Resolvable Private Addresses that have already been matched against a peer IRK by
+    /// `resolve_peer_address`, keyed by the address, so a rotating address that is looked up
+    /// repeatedly only pays for the IRK scan once.
+    resolved_rpas: HashMap<String, String>,
+
+    /// Every peer address recently observed for each bonded identity (keyed by `BondingData`
+    /// identifier), so earlier sightings of a peer that rotates its address are kept alongside
+    /// its latest one. `resolve_peer_address` does not consult this set. See `gc_expired`.
+    observed_addresses: HashMap<String, Vec<ObservedAddress>>,
+
+    /// Bonds quarantined by the last `new()` because they failed their checksum or failed to
+    /// deserialize, as `(key, raw value)` pairs. Drained by `recover_quarantined`.
+    quarantined: Vec<(String, String)>,
+
+    /// Mirrors `quarantined.len()` on the inspect tree so operators can see at a glance whether
+    /// this stash has unreadable bonds without querying the API.
+    quarantine_count: fuchsia_inspect::UintProperty,
+
+    /// Writes enqueued by `store_bond`/`store_host_data`/`rm_peer` since the last flush, in
+    /// enqueue order. Drained by the background flush task spawned in `new`, or immediately by
+    /// `flush`. Shared with that task via `Rc`/`RefCell` rather than passed through `&mut self`,
+    /// since the task runs independently of any particular call to a `Stash` method.
+    pending_writes: Rc<RefCell<Vec<(String, Option<Value>)>>>,
+}
+
+/// How long an address is kept in a bond's observed-address set by `gc_expired` since it was last
+/// seen, absent a fresher sighting.
+fn observed_address_ttl() -> Duration {
+    Duration::from_hours(24)
+}
+
+/// A peer address sighting for a bonded identity, together with when it was last observed.
+#[derive(Debug, Clone)]
+struct ObservedAddress {
+    address: String,
+    last_seen: Time,
+}
+
+/// Returns every peer address recorded on a bond: its LE address, its BR/EDR address, or both if
+/// it's a dual-mode peer.
+fn peer_addresses(data: &BondingData) -> Vec<String> {
+    let mut addresses = Vec::new();
+    if let Some(le) = &data.le {
+        addresses.push(le.address.clone());
+    }
+    if let Some(bredr) = &data.bredr {
+        addresses.push(bredr.address.clone());
+    }
+    addresses
+}
+
+/// Counts of bonding/host data entries that loaded successfully vs. were skipped as corrupt the
+/// last time a `Stash` was initialized. Returned by `Stash::load_report` for inspect.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoadReport {
+    pub bonds_loaded: usize,
+    pub bonds_skipped: usize,
+    pub host_data_loaded: usize,
+    pub host_data_skipped: usize,
+}
+
+/// Prefix under which a bonding data entry that failed its integrity check or failed to
+/// deserialize is quarantined, so the raw value is preserved for investigation instead of being
+/// silently dropped.
+const CORRUPT_BONDING_DATA_PREFIX: &str = "corrupt-bonding-data:";
+
+/// Returns the key under which the integrity checksum for `key`'s value is stored.
+fn checksum_key(key: &str) -> String {
+    format!("checksum:{}", key)
+}
+
+/// Computes the integrity checksum persisted alongside a stash value: the hex-encoded SHA-256
+/// digest of the value's UTF-8 bytes exactly as stored (i.e. after sealing, if encryption is
+/// enabled).
+fn checksum(value: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(value.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Verifies the checksum persisted under `checksum_key(key)` against `value`. A missing checksum
+/// (e.g. a pre-checksum entry) is accepted, so existing stashes keep loading and gain a checksum
+/// the next time the entry is written.
+async fn verify_checksum(
+    accessor: &StoreAccessorProxy,
+    key: &str,
+    value: &str,
+) -> Result<(), Error> {
+    match accessor.get_value(&checksum_key(key)).await? {
+        Some(boxed) => match *boxed {
+            Value::Stringval(expected) if expected == checksum(value) => Ok(()),
+            Value::Stringval(_) => Err(BtError::new("checksum mismatch").into()),
+            _ => Err(BtError::new("stash malformed: checksum should be a string").into()),
+        },
+        None => Ok(()),
+    }
+}
+
+/// The key under which the random salt used to derive the stash encryption key is persisted. This
+/// is read on every `Stash::new` so the same passphrase always derives the same key.
+const CRYPTO_SALT_KEY: &str = "stash-crypto-salt";
+const CRYPTO_SALT_LEN: usize = 16;
+const CRYPTO_NONCE_LEN: usize = 12;
+
+/// Seals bonding and host data blobs at rest. A 32-byte key is derived from a caller-supplied
+/// passphrase with Argon2id and a per-stash random salt, then used with ChaCha20-Poly1305 to
+/// encrypt each serialized JSON blob individually, so entries written while encryption is enabled
+/// don't expose LTKs/IRKs in the clear. Entries written before encryption was enabled stay in
+/// plaintext until they are next written (see `maybe_decrypt`).
+struct StashCrypto {
+    key: Key,
+}
+
+impl std::fmt::Debug for StashCrypto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StashCrypto").finish()
+    }
+}
+
+impl StashCrypto {
+    /// Derives the stash encryption key from `passphrase`, generating and persisting a random
+    /// salt under `CRYPTO_SALT_KEY` the first time this identity enables encryption.
+    async fn new(accessor: &StoreAccessorProxy, passphrase: &[u8]) -> Result<StashCrypto, Error> {
+        let salt = match accessor.get_value(CRYPTO_SALT_KEY).await? {
+            Some(boxed) => match *boxed {
+                Value::Stringval(b64) => base64::decode(&b64)?,
+                _ => {
+                    return Err(BtError::new("stash malformed: crypto salt should be a string")
+                        .into());
+                }
+            },
+            None => {
+                let mut salt = vec![0; CRYPTO_SALT_LEN];
+                cprng_draw(&mut salt)?;
+                accessor.set_value(CRYPTO_SALT_KEY, &mut Value::Stringval(base64::encode(&salt)))?;
+                accessor.commit()?;
+                salt
+            }
+        };
+        StashCrypto::from_passphrase(passphrase, &salt)
+    }
+
+    /// Derives a key from `passphrase` and `salt` directly, without persisting the salt anywhere.
+    /// Used for one-off sealing such as an export archive, where the salt travels with the sealed
+    /// data instead of living in the stash.
+    fn from_passphrase(passphrase: &[u8], salt: &[u8]) -> Result<StashCrypto, Error> {
+        let mut derived = [0; 32];
+        argon2::Argon2::default()
+            .hash_password_into(passphrase, salt, &mut derived)
+            .map_err(|e| BtError::new(&format!("failed to derive stash crypto key: {}", e)))?;
+        Ok(StashCrypto { key: *Key::from_slice(&derived) })
+    }
+
+    /// Seals `plaintext`, returning `nonce || ciphertext || tag` as base64.
+    fn seal(&self, plaintext: &str) -> Result<String, Error> {
+        let cipher = ChaCha20Poly1305::new(&self.key);
+        let mut nonce_bytes = [0; CRYPTO_NONCE_LEN];
+        cprng_draw(&mut nonce_bytes)?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), plaintext.as_bytes())
+            .map_err(|_| BtError::new("failed to seal stash entry"))?;
+        let mut sealed = Vec::with_capacity(nonce_bytes.len() + ciphertext.len());
+        sealed.extend_from_slice(&nonce_bytes);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(base64::encode(&sealed))
+    }
+
+    /// Reverses `seal`, returning a clear error if the blob is short or the tag fails to verify.
+    fn open(&self, sealed: &str) -> Result<String, Error> {
+        let sealed = base64::decode(sealed)?;
+        if sealed.len() < CRYPTO_NONCE_LEN {
+            return Err(BtError::new("stash entry too short to contain a nonce").into());
+        }
+        let (nonce_bytes, ciphertext) = sealed.split_at(CRYPTO_NONCE_LEN);
+        let cipher = ChaCha20Poly1305::new(&self.key);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| BtError::new("failed to open stash entry: authentication failed"))?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+}
+
+/// Decrypts `raw` if `crypto` is configured. Data written before encryption was enabled is not
+/// sealed; `StashCrypto::open` fails fast on such input (it isn't valid base64-framed ciphertext),
+/// so this falls back to treating it as plaintext, letting existing stashes keep loading and
+/// migrate to sealed storage the next time the entry is written.
+fn maybe_decrypt(raw: String, crypto: Option<&StashCrypto>) -> Result<String, Error> {
+    match crypto {
+        Some(crypto) => Ok(crypto.open(&raw).unwrap_or(raw)),
+        None => Ok(raw),
+    }
+}
+
+/// Preserves a bonding or host data entry that failed its checksum or failed to deserialize under
+/// `CORRUPT_BONDING_DATA_PREFIX` for later investigation, then removes the original entry and its
+/// checksum so it is not retried on the next load. Does not commit; the caller commits once after
+/// quarantining all corrupt entries found in a load pass.
+fn quarantine(accessor: &StoreAccessorProxy, key: &str, raw: &str) -> Result<(), Error> {
+    accessor.set_value(
+        &format!("{}{}", CORRUPT_BONDING_DATA_PREFIX, key),
+        &mut Value::Stringval(raw.to_string()),
+    )?;
+    accessor.delete_value(key)?;
+    accessor.delete_value(&checksum_key(key))?;
+    Ok(())
+}
+
+/// How often the background flush task spawned by `Stash::new` drains `pending_writes` and
+/// issues everything queued since the last drain as a single `commit()`. Kept short so a single
+/// isolated write still reaches the stash promptly, while a burst of writes (e.g. restoring many
+/// bonds during a reconnection storm) collapses onto one `commit()` instead of one per write.
+fn flush_interval() -> Duration {
+    Duration::from_millis(20)
+}
+
+/// Drains every write enqueued in `pending` and issues them as a single `commit()`, in the order
+/// they were enqueued, so a delete followed by a re-insert of the same key is applied
+/// delete-then-insert rather than being collapsed or reordered. Does nothing if `pending` is
+/// empty.
+fn drain_pending_writes(
+    proxy: &StoreAccessorProxy,
+    pending: &Rc<RefCell<Vec<(String, Option<Value>)>>>,
+) -> Result<(), Error> {
+    let writes = std::mem::replace(&mut *pending.borrow_mut(), Vec::new());
+    if writes.is_empty() {
+        return Ok(());
+    }
+    for (key, value) in writes {
+        match value {
+            Some(mut value) => proxy.set_value(&key, &mut value)?,
+            None => proxy.delete_value(&key)?,
+        }
+    }
+    proxy.commit()
+}
+
+/// Spawns the background task that coalesces writes enqueued by `store_bond`/`store_host_data`/
+/// `rm_peer`: wakes every `flush_interval()` and drains whatever has accumulated in `pending`
+/// with a single `commit()`. The task is detached and outlives any particular `Stash` call; it
+/// holds its own clone of `proxy` and `pending` rather than borrowing from a `Stash`.
+fn spawn_flush_task(proxy: StoreAccessorProxy, pending: Rc<RefCell<Vec<(String, Option<Value>)>>>) {
+    fasync::spawn_local(async move {
+        let mut interval = fasync::Interval::new(flush_interval());
+        while interval.next().await.is_some() {
+            if let Err(e) = drain_pending_writes(&proxy, &pending) {
+                fx_log_err!("failed to flush stash writes: {}", e);
+            }
+        }
+    });
+}
+
+/// Parses a colon-separated hex Bluetooth device address (e.g. "01:02:03:04:05:06") into its
+/// bytes, most significant octet first.
+fn parse_address(addr: &str) -> Option<[u8; 6]> {
+    let mut bytes = [0u8; 6];
+    let mut octets = addr.split(':');
+    for byte in bytes.iter_mut() {
+        *byte = u8::from_str_radix(octets.next()?, 16).ok()?;
+    }
+    if octets.next().is_some() {
+        return None;
+    }
+    Some(bytes)
+}
+
+/// Implements the BLE `ah` function (Core Spec v5.2, Vol 3, Part H, 2.3.5.4): encrypts `prand`,
+/// zero-padded up to a 16-byte block, with `irk` under AES-128-ECB and returns the least
+/// significant 24 bits of the ciphertext. This is the first half of resolving a Resolvable
+/// Private Address against an Identity Resolving Key; the caller compares the result to the
+/// address's `hash` half.
+fn ah(irk: &[u8; 16], prand: &[u8; 3]) -> [u8; 3] {
+    let mut r_prime = GenericArray::<u8, _>::default();
+    r_prime[13..16].copy_from_slice(prand);
+    let cipher = Aes128::new(GenericArray::from_slice(irk));
+    cipher.encrypt_block(&mut r_prime);
+    let mut hash = [0; 3];
+    hash.copy_from_slice(&r_prime[13..16]);
+    hash
+}
+
+/// Version of the archive format produced by `Stash::export_all`. Bumped whenever the fields of
+/// `StashArchive` change in a way `Stash::import` can't read across.
+const ARCHIVE_VERSION: u32 = 1;
+
+/// The contents of a bonding backup: every bonding and host data entry, still in their
+/// already-serialized JSON form (the same blobs the live stash persists), so sealing the whole
+/// archive doesn't require round-tripping through the serde wrapper types twice.
+#[derive(Serialize, Deserialize)]
+struct StashArchive {
+    version: u32,
+    bonds: Vec<String>,
+    hosts: Vec<(String, String)>,
+}
+
+/// The opaque form returned by `Stash::export_all`: a fresh random salt alongside the
+/// passphrase-sealed `StashArchive`, so `Stash::import` can re-derive the same key used to seal
+/// it without the salt needing to be communicated out of band.
+#[derive(Serialize, Deserialize)]
+struct SealedArchive {
+    salt: String,
+    sealed: String,
 }
 
 impl Stash {
@@ -84,13 +404,21 @@ impl Stash {
         let data = Inspectable::new(data, node);
         fx_log_info!("store_bond (id: {})", data.identifier);
 
-        // Persist the serialized blob.
+        // Persist the serialized blob, sealed if encryption is enabled for this stash.
         let serialized = serde_json::to_string(&BondingDataSerializer(&data.clone().into()))?;
-        self.proxy
-            .set_value(&bonding_data_key(&data.identifier), &mut Value::Stringval(serialized))?;
-        self.proxy.commit()?;
+        let to_store = match &self.crypto {
+            Some(crypto) => crypto.seal(&serialized)?,
+            None => serialized,
+        };
+        let key = bonding_data_key(&data.identifier);
+        {
+            let mut pending = self.pending_writes.borrow_mut();
+            pending.push((checksum_key(&key), Some(Value::Stringval(checksum(&to_store)))));
+            pending.push((key, Some(Value::Stringval(to_store))));
+        }
 
         // Update the in memory cache.
+        self.record_observed_addresses(&data);
         let local_map =
             self.bonding_data.entry(data.local_address.clone()).or_insert(HashMap::new());
         local_map.insert(data.identifier.clone(), data);
@@ -114,15 +442,81 @@ impl Stash {
     pub fn rm_peer(&mut self, peer_id: &str) -> Result<(), Error> {
         fx_log_info!("rm_peer (id: {})", peer_id);
 
-        // Delete the persisted bond blob.
-        self.proxy.delete_value(&bonding_data_key(&peer_id))?;
-        self.proxy.commit()?;
+        // Enqueue deletion of the persisted bond blob and its checksum.
+        let key = bonding_data_key(&peer_id);
+        {
+            let mut pending = self.pending_writes.borrow_mut();
+            pending.push((checksum_key(&key), None));
+            pending.push((key, None));
+        }
 
         // Delete peer from memory cache of all adapters.
         self.bonding_data.values_mut().for_each(|m| m.retain(|k, _| k != peer_id));
+        self.resolved_rpas.retain(|_, identifier| identifier != peer_id);
+        self.observed_addresses.remove(peer_id);
         Ok(())
     }
 
+    /// Merges a bond's current peer addresses into its observed-address set, refreshing the
+    /// timestamp of any address already present instead of clobbering earlier sightings.
+    fn record_observed_addresses(&mut self, data: &BondingData) {
+        let addresses = peer_addresses(data);
+        if addresses.is_empty() {
+            return;
+        }
+        let now = Time::get_monotonic();
+        let sightings =
+            self.observed_addresses.entry(data.identifier.clone()).or_insert(Vec::new());
+        for address in addresses {
+            match sightings.iter_mut().find(|sighting| sighting.address == address) {
+                Some(sighting) => sighting.last_seen = now,
+                None => sightings.push(ObservedAddress { address, last_seen: now }),
+            }
+        }
+    }
+
+    /// Prunes observed peer addresses that haven't been seen within `observed_address_ttl()`,
+    /// and drops the observed-address set of any bond identity left with no addresses at all.
+    /// The bonds themselves are kept.
+    pub fn gc_expired(&mut self) {
+        let now = Time::get_monotonic();
+        let ttl = observed_address_ttl();
+        self.observed_addresses.retain(|_, sightings| {
+            sightings.retain(|sighting| now - sighting.last_seen < ttl);
+            !sightings.is_empty()
+        });
+    }
+
+    /// Resolves a peer's Resolvable Private Address against the IRK of every stored bond,
+    /// returning the matching `BondingData` identifier. Short-circuits on the first match, and
+    /// caches the result so repeated lookups of the same rotating address are O(1).
+    pub fn resolve_peer_address(&mut self, addr: &str) -> Option<String> {
+        if let Some(identifier) = self.resolved_rpas.get(addr) {
+            return Some(identifier.clone());
+        }
+
+        let address = parse_address(addr)?;
+        // A Resolvable Private Address's most significant octet has its top two bits set to
+        // `01`. The upper 24 bits (including those two) are `prand`, the lower 24 bits `hash`.
+        if address[0] & 0xc0 != 0x40 {
+            return None;
+        }
+        let prand = [address[0], address[1], address[2]];
+        let hash = [address[3], address[4], address[5]];
+
+        let identifier = self.bonding_data.values().flat_map(HashMap::values).find_map(|data| {
+            let irk = &data.le.as_ref()?.irk.as_ref()?.value;
+            if ah(irk, &prand) == hash {
+                Some(data.identifier.clone())
+            } else {
+                None
+            }
+        })?;
+
+        self.resolved_rpas.insert(addr.to_string(), identifier.clone());
+        Some(identifier)
+    }
+
     /// Returns the local host data for the given local `address`.
     pub fn get_host_data(&self, local_address: &str) -> Option<&HostData> {
         self.host_data.get(local_address)
@@ -132,36 +526,190 @@ impl Stash {
     pub fn store_host_data(&mut self, local_addr: &str, data: HostData) -> Result<(), Error> {
         fx_log_info!("store_host_data (local address: {})", local_addr);
 
-        // Persist the serialized blob.
+        // Persist the serialized blob, sealed if encryption is enabled for this stash.
         let serialized = serde_json::to_string(&HostDataSerializer(&data))?;
-        self.proxy.set_value(&host_data_key(local_addr), &mut Value::Stringval(serialized))?;
-        self.proxy.commit()?;
+        let to_store = match &self.crypto {
+            Some(crypto) => crypto.seal(&serialized)?,
+            None => serialized,
+        };
+        let key = host_data_key(local_addr);
+        {
+            let mut pending = self.pending_writes.borrow_mut();
+            pending.push((checksum_key(&key), Some(Value::Stringval(checksum(&to_store)))));
+            pending.push((key, Some(Value::Stringval(to_store))));
+        }
 
         // Update the in memory cache.
         self.host_data.insert(local_addr.to_string(), data);
         Ok(())
     }
 
+    /// Immediately drains any writes enqueued by `store_bond`/`store_host_data`/`rm_peer` since
+    /// the last flush and issues them as a single `commit()`, without waiting for the background
+    /// flush task's next `flush_interval()` tick. Callers that need a durability guarantee (e.g.
+    /// before replying to a FIDL request that promised the write is persisted, or at shutdown)
+    /// should await this rather than relying on the background task alone.
+    pub async fn flush(&self) -> Result<(), Error> {
+        drain_pending_writes(&self.proxy, &self.pending_writes)
+    }
+
+    /// Opens a `Batch` that collects `store_bond`/`store_host_data`/`rm_peer` calls and persists
+    /// them with a single `commit()` on `Batch::flush` (or on drop), instead of one round-trip
+    /// per call. See `Batch`.
+    pub fn batch(&mut self) -> Batch<'_> {
+        Batch { stash: self, writes: Vec::new(), cache_updates: Vec::new(), flushed: false }
+    }
+
+    /// Stores many bonds with a single stash commit. Equivalent to calling `store_bond` for each
+    /// item of `bonds` within one `Batch`.
+    pub fn store_bonds(
+        &mut self,
+        bonds: impl IntoIterator<Item = BondingData>,
+    ) -> Result<(), Error> {
+        let mut batch = self.batch();
+        for data in bonds {
+            batch.store_bond(data)?;
+        }
+        batch.flush()
+    }
+
+    /// Gathers every bonding and host data entry into a single archive, sealed with a key derived
+    /// from `passphrase` via Argon2id, for a user migrating bonds to a new device. The returned
+    /// bytes are opaque and only decodable with the same passphrase via `import`.
+    pub fn export_all(&self, passphrase: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut bonds = Vec::new();
+        for local_address_entries in self.bonding_data.values() {
+            for data in local_address_entries.values() {
+                bonds.push(serde_json::to_string(&BondingDataSerializer(&data.clone().into()))?);
+            }
+        }
+        let hosts = self
+            .host_data
+            .iter()
+            .map(|(local_addr, data)| {
+                Ok((local_addr.clone(), serde_json::to_string(&HostDataSerializer(data))?))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let archive =
+            serde_json::to_string(&StashArchive { version: ARCHIVE_VERSION, bonds, hosts })?;
+
+        let mut salt = vec![0; CRYPTO_SALT_LEN];
+        cprng_draw(&mut salt)?;
+        let crypto = StashCrypto::from_passphrase(passphrase, &salt)?;
+        let sealed = SealedArchive { salt: base64::encode(&salt), sealed: crypto.seal(&archive)? };
+        Ok(serde_json::to_vec(&sealed)?)
+    }
+
+    /// Restores bonds and host data from an `export_all` archive, sealed with the same
+    /// `passphrase`. When `merge` is `false`, all existing bonds are removed first; otherwise
+    /// imported entries are added alongside (and, on a colliding identifier, overwrite) what is
+    /// already stored. Both the persisted stash and in-memory caches are populated.
+    pub fn import(&mut self, archive: &[u8], passphrase: &[u8], merge: bool) -> Result<(), Error> {
+        let sealed: SealedArchive = serde_json::from_slice(archive)?;
+        let salt = base64::decode(&sealed.salt)?;
+        let crypto = StashCrypto::from_passphrase(passphrase, &salt)?;
+        let archive: StashArchive = serde_json::from_str(&crypto.open(&sealed.sealed)?)?;
+        if archive.version != ARCHIVE_VERSION {
+            return Err(BtError::new(&format!(
+                "unsupported stash archive version {} (expected {})",
+                archive.version, ARCHIVE_VERSION
+            ))
+            .into());
+        }
+
+        if !merge {
+            let peer_ids: Vec<String> =
+                self.bonding_data.values().flat_map(HashMap::keys).cloned().collect();
+            for peer_id in peer_ids {
+                self.rm_peer(&peer_id)?;
+            }
+        }
+
+        let mut batch = self.batch();
+        for bond in archive.bonds {
+            let bonding_data: BondingDataDeserializer = serde_json::from_str(&bond)?;
+            batch.store_bond(BondingData::from(bonding_data.contents()))?;
+        }
+        for (local_addr, host) in archive.hosts {
+            let host_data: HostDataDeserializer = serde_json::from_str(&host)?;
+            batch.store_host_data(&local_addr, host_data.contents())?;
+        }
+        batch.flush()
+    }
+
     // Initializes the stash using the given `accessor`. This asynchronously loads existing
-    // stash data. Returns an error in case of failure.
+    // stash data. Returns an error in case of failure. If `passphrase` is given, stash entries
+    // are sealed at rest; see `StashCrypto`.
     async fn new(
         accessor: StoreAccessorProxy,
         inspect: fuchsia_inspect::Node,
+        passphrase: Option<&[u8]>,
     ) -> Result<Stash, Error> {
-        let bonding_data = Stash::load_bonds(&accessor, &inspect).await?;
-        let host_data = Stash::load_host_data(&accessor).await?;
-        Ok(Stash { proxy: accessor, bonding_data, host_data, inspect })
+        let crypto = match passphrase {
+            Some(passphrase) => Some(StashCrypto::new(&accessor, passphrase).await?),
+            None => None,
+        };
+        let (bonding_data, quarantined) =
+            Stash::load_bonds(&accessor, &inspect, crypto.as_ref()).await?;
+        let (host_data, host_data_skipped) =
+            Stash::load_host_data(&accessor, crypto.as_ref()).await?;
+        let load_report = LoadReport {
+            bonds_loaded: bonding_data.values().map(HashMap::len).sum(),
+            bonds_skipped: quarantined.len(),
+            host_data_loaded: host_data.len(),
+            host_data_skipped,
+        };
+        let quarantine_count = inspect.create_uint("quarantined_bonds", quarantined.len() as u64);
+        let pending_writes = Rc::new(RefCell::new(Vec::new()));
+        spawn_flush_task(accessor.clone(), pending_writes.clone());
+        Ok(Stash {
+            proxy: accessor,
+            bonding_data,
+            host_data,
+            inspect,
+            crypto,
+            load_report,
+            resolved_rpas: HashMap::new(),
+            observed_addresses: HashMap::new(),
+            quarantined,
+            quarantine_count,
+            pending_writes,
+        })
+    }
+
+    /// Returns and clears the bonds quarantined by the last `new()`, so an operator (e.g. a
+    /// support tool driving this API) can inspect or act on unreadable bonds. The entries are
+    /// already removed from the stash itself by the time they appear here; this only clears them
+    /// from the in-memory side-list and its inspect count.
+    pub fn recover_quarantined(&mut self) -> Vec<(String, String)> {
+        self.quarantine_count.set(0);
+        std::mem::replace(&mut self.quarantined, Vec::new())
+    }
+
+    /// Reports how many entries were loaded, and how many were skipped as corrupt, by the most
+    /// recent `new()`.
+    pub fn load_report(&self) -> LoadReport {
+        self.load_report
     }
 
+    // Loads bonding data, tolerating corrupt entries: any entry that fails its checksum or fails
+    // to decrypt/deserialize is moved aside under `CORRUPT_BONDING_DATA_PREFIX` rather than
+    // aborting initialization of the whole stash. Returns the loaded map and the skipped entries
+    // as `(key, raw value)` pairs.
     async fn load_bonds<'a>(
         accessor: &'a StoreAccessorProxy,
         inspect: &'a fuchsia_inspect::Node,
-    ) -> Result<HashMap<String, HashMap<String, Inspectable<BondingData>>>, Error> {
+        crypto: Option<&StashCrypto>,
+    ) -> Result<
+        (HashMap<String, HashMap<String, Inspectable<BondingData>>>, Vec<(String, String)>),
+        Error,
+    > {
         // Obtain a list iterator for all cached bonding data.
         let (iter, server_end) = create_proxy::<GetIteratorMarker>()?;
         accessor.get_prefix(BONDING_DATA_PREFIX, server_end)?;
 
         let mut bonding_map = HashMap::new();
+        let mut quarantined = Vec::new();
         loop {
             let next = iter.get_next().await?;
             if next.is_empty() {
@@ -169,8 +717,25 @@ impl Stash {
             }
             for key_value in next {
                 if let Value::Stringval(json) = key_value.val {
-                    let bonding_data: BondingDataDeserializer = serde_json::from_str(&json)?;
-                    let bonding_data = BondingData::from(bonding_data.contents());
+                    if let Err(e) = verify_checksum(accessor, &key_value.key, &json).await {
+                        fx_log_err!("bonding data {} failed checksum: {}", key_value.key, e);
+                        quarantine(accessor, &key_value.key, &json)?;
+                        quarantined.push((key_value.key, json));
+                        continue;
+                    }
+                    let loaded = maybe_decrypt(json.clone(), crypto).and_then(|json| {
+                        let bonding_data: BondingDataDeserializer = serde_json::from_str(&json)?;
+                        Ok(BondingData::from(bonding_data.contents()))
+                    });
+                    let bonding_data = match loaded {
+                        Ok(bonding_data) => bonding_data,
+                        Err(e) => {
+                            fx_log_err!("bonding data {} is corrupt: {}", key_value.key, e);
+                            quarantine(accessor, &key_value.key, &json)?;
+                            quarantined.push((key_value.key, json));
+                            continue;
+                        }
+                    };
                     let node = inspect.create_child(format!("bond {}", bonding_data.identifier));
                     let bonding_data = Inspectable::new(bonding_data, node);
                     let local_address_entries = bonding_map
@@ -178,22 +743,34 @@ impl Stash {
                         .or_insert(HashMap::new());
                     local_address_entries.insert(bonding_data.identifier.clone(), bonding_data);
                 } else {
-                    fx_log_err!("stash malformed: bonding data should be a string");
-                    return Err(BtError::new("failed to initialize stash").into());
+                    fx_log_err!(
+                        "stash malformed: bonding data {} should be a string",
+                        key_value.key
+                    );
+                    quarantine(accessor, &key_value.key, "<non-string value>")?;
+                    quarantined.push((key_value.key, "<non-string value>".to_string()));
                 }
             }
         }
-        Ok(bonding_map)
+        if !quarantined.is_empty() {
+            accessor.commit()?;
+        }
+        Ok((bonding_map, quarantined))
     }
 
+    // Loads host data, tolerating corrupt entries the same way `load_bonds` does. Returns the
+    // loaded map and the number of entries skipped.
     async fn load_host_data(
         accessor: &StoreAccessorProxy,
-    ) -> Result<HashMap<String, HostData>, Error> {
+        crypto: Option<&StashCrypto>,
+    ) -> Result<(HashMap<String, HostData>, usize), Error> {
         // Obtain a list iterator for all cached host data.
         let (iter, server_end) = create_proxy::<GetIteratorMarker>()?;
         accessor.get_prefix(HOST_DATA_PREFIX, server_end)?;
 
         let mut host_data_map = HashMap::new();
+        let mut skipped = 0;
+        let mut quarantined = false;
         loop {
             let next = iter.get_next().await?;
             if next.is_empty() {
@@ -202,16 +779,40 @@ impl Stash {
             for key_value in next {
                 let host_id = host_id_from_key(&key_value.key)?;
                 if let Value::Stringval(json) = key_value.val {
-                    let host_data: HostDataDeserializer = serde_json::from_str(&json)?;
-                    let host_data = host_data.contents();
+                    if let Err(e) = verify_checksum(accessor, &key_value.key, &json).await {
+                        fx_log_err!("host data {} failed checksum: {}", key_value.key, e);
+                        quarantine(accessor, &key_value.key, &json)?;
+                        quarantined = true;
+                        skipped += 1;
+                        continue;
+                    }
+                    let loaded = maybe_decrypt(json.clone(), crypto).and_then(|json| {
+                        let host_data: HostDataDeserializer = serde_json::from_str(&json)?;
+                        Ok(host_data.contents())
+                    });
+                    let host_data = match loaded {
+                        Ok(host_data) => host_data,
+                        Err(e) => {
+                            fx_log_err!("host data {} is corrupt: {}", key_value.key, e);
+                            quarantine(accessor, &key_value.key, &json)?;
+                            quarantined = true;
+                            skipped += 1;
+                            continue;
+                        }
+                    };
                     host_data_map.insert(host_id, host_data);
                 } else {
-                    fx_log_err!("stash malformed: host data should be a string");
-                    return Err(BtError::new("failed to initialize stash").into());
+                    fx_log_err!("stash malformed: host data {} should be a string", key_value.key);
+                    quarantine(accessor, &key_value.key, "<non-string value>")?;
+                    quarantined = true;
+                    skipped += 1;
                 }
             }
         }
-        Ok(host_data_map)
+        if quarantined {
+            accessor.commit()?;
+        }
+        Ok((host_data_map, skipped))
     }
 
     #[cfg(test)]
@@ -220,7 +821,150 @@ impl Stash {
         let proxy = fasync::Channel::from_channel(proxy)?;
         let proxy = StoreAccessorProxy::from_channel(proxy);
         let inspect = fuchsia_inspect::Inspector::new().root().create_child("stub inspect");
-        Ok(Stash { proxy, bonding_data: HashMap::new(), host_data: HashMap::new(), inspect })
+        let quarantine_count = inspect.create_uint("quarantined_bonds", 0);
+        let pending_writes = Rc::new(RefCell::new(Vec::new()));
+        spawn_flush_task(proxy.clone(), pending_writes.clone());
+        Ok(Stash {
+            proxy,
+            bonding_data: HashMap::new(),
+            host_data: HashMap::new(),
+            inspect,
+            crypto: None,
+            load_report: LoadReport::default(),
+            resolved_rpas: HashMap::new(),
+            observed_addresses: HashMap::new(),
+            quarantined: Vec::new(),
+            quarantine_count,
+            pending_writes,
+        })
+    }
+}
+
+/// An in-memory cache update deferred until a `Batch`'s writes have been committed.
+enum PendingWrite {
+    Bond(Inspectable<BondingData>),
+    RemoveBond(String),
+    HostData(String, HostData),
+}
+
+/// Collects `store_bond`/`store_host_data`/`rm_peer` calls and persists them with a single
+/// `commit()` instead of one per call, so restoring many bonds at boot (or clearing many) costs
+/// one FIDL round-trip rather than N. The in-memory cache is only updated once the commit
+/// succeeds, so a failed commit leaves `Stash`'s cache exactly as it was before the batch.
+///
+/// Obtained via `Stash::batch`. Call `flush` for an explicit result, or simply drop the batch to
+/// flush it implicitly (drop logs and discards any error, since there is no caller left to
+/// propagate it to).
+pub struct Batch<'a> {
+    stash: &'a mut Stash,
+    writes: Vec<(String, Option<Value>)>,
+    cache_updates: Vec<PendingWrite>,
+    flushed: bool,
+}
+
+impl<'a> Batch<'a> {
+    /// Queues a bond to be stored. Not persisted or visible via `Stash` accessors until `flush`.
+    pub fn store_bond(&mut self, data: BondingData) -> Result<(), Error> {
+        let node = self.stash.inspect.create_child(format!("bond {}", data.identifier));
+        let data = Inspectable::new(data, node);
+
+        let serialized = serde_json::to_string(&BondingDataSerializer(&data.clone().into()))?;
+        let to_store = match &self.stash.crypto {
+            Some(crypto) => crypto.seal(&serialized)?,
+            None => serialized,
+        };
+        let key = bonding_data_key(&data.identifier);
+        self.writes.push((checksum_key(&key), Some(Value::Stringval(checksum(&to_store)))));
+        self.writes.push((key, Some(Value::Stringval(to_store))));
+        self.cache_updates.push(PendingWrite::Bond(data));
+        Ok(())
+    }
+
+    /// Queues a peer's bond to be removed. Not persisted or visible via `Stash` accessors until
+    /// `flush`.
+    pub fn rm_peer(&mut self, peer_id: &str) -> Result<(), Error> {
+        let key = bonding_data_key(peer_id);
+        self.writes.push((checksum_key(&key), None));
+        self.writes.push((key, None));
+        self.cache_updates.push(PendingWrite::RemoveBond(peer_id.to_string()));
+        Ok(())
+    }
+
+    /// Queues host data to be stored. Not persisted or visible via `Stash` accessors until
+    /// `flush`.
+    pub fn store_host_data(&mut self, local_addr: &str, data: HostData) -> Result<(), Error> {
+        let serialized = serde_json::to_string(&HostDataSerializer(&data))?;
+        let to_store = match &self.stash.crypto {
+            Some(crypto) => crypto.seal(&serialized)?,
+            None => serialized,
+        };
+        let key = host_data_key(local_addr);
+        self.writes.push((checksum_key(&key), Some(Value::Stringval(checksum(&to_store)))));
+        self.writes.push((key, Some(Value::Stringval(to_store))));
+        self.cache_updates.push(PendingWrite::HostData(local_addr.to_string(), data));
+        Ok(())
+    }
+
+    /// Issues all pending writes as a single `commit()`. Only once that commit succeeds are the
+    /// queued changes applied to `Stash`'s in-memory cache.
+    pub fn flush(mut self) -> Result<(), Error> {
+        self.commit_pending()
+    }
+
+    fn commit_pending(&mut self) -> Result<(), Error> {
+        if self.flushed {
+            return Ok(());
+        }
+        self.flushed = true;
+
+        // Drain any writes enqueued ambiently by `store_bond`/`store_host_data`/`rm_peer` first,
+        // so this batch's writes land after (not interleaved arbitrarily with) writes that were
+        // already queued when the batch was opened.
+        drain_pending_writes(&self.stash.proxy, &self.stash.pending_writes)?;
+
+        for (key, value) in &mut self.writes {
+            match value {
+                Some(value) => self.stash.proxy.set_value(key, value)?,
+                None => self.stash.proxy.delete_value(key)?,
+            }
+        }
+        self.stash.proxy.commit()?;
+
+        // The commit succeeded: only now is it safe to fold the queued writes into the
+        // in-memory cache.
+        for update in self.cache_updates.drain(..) {
+            match update {
+                PendingWrite::Bond(data) => {
+                    self.stash.record_observed_addresses(&data);
+                    let local_map = self
+                        .stash
+                        .bonding_data
+                        .entry(data.local_address.clone())
+                        .or_insert(HashMap::new());
+                    local_map.insert(data.identifier.clone(), data);
+                }
+                PendingWrite::RemoveBond(peer_id) => {
+                    self.stash
+                        .bonding_data
+                        .values_mut()
+                        .for_each(|m| m.retain(|k, _| k != &peer_id));
+                    self.stash.resolved_rpas.retain(|_, identifier| identifier != &peer_id);
+                    self.stash.observed_addresses.remove(&peer_id);
+                }
+                PendingWrite::HostData(local_addr, data) => {
+                    self.stash.host_data.insert(local_addr, data);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Drop for Batch<'a> {
+    fn drop(&mut self) {
+        if let Err(e) = self.commit_pending() {
+            fx_log_err!("failed to flush stash batch: {}", e);
+        }
     }
 }
 
@@ -236,7 +980,7 @@ pub async fn init_stash(
     let (proxy, server_end) = create_proxy::<StoreAccessorMarker>()?;
     stash_svc.create_accessor(false, server_end)?;
 
-    Stash::new(proxy, inspect).await
+    Stash::new(proxy, inspect, None).await
 }
 
 // These tests access stash in a hermetic envionment and thus it's ok for state to leak between
@@ -279,7 +1023,7 @@ mod tests {
         // Create a Stash service interface.
         let accessor_proxy = create_stash_accessor("new_stash_succeeds_with_empty_values")
             .expect("failed to create StashAccessor");
-        let stash_new_future = Stash::new(accessor_proxy, inspect);
+        let stash_new_future = Stash::new(accessor_proxy, inspect, None);
         pin_mut!(stash_new_future);
 
         // The stash should be initialized with no data.
@@ -291,14 +1035,14 @@ mod tests {
     }
 
     #[test]
-    fn new_stash_fails_with_malformed_key_value_entry() {
+    fn new_stash_skips_malformed_key_value_entry() {
         let mut exec = fasync::Executor::new().expect("failed to create an executor");
 
         let inspect = fuchsia_inspect::Inspector::new().root().create_child("test");
 
         // Create a Stash service interface.
         let accessor_proxy =
-            create_stash_accessor("new_stash_fails_with_malformed_key_value_entry")
+            create_stash_accessor("new_stash_skips_malformed_key_value_entry")
                 .expect("failed to create StashAccessor");
 
         // Set a key/value that contains a non-string value.
@@ -307,19 +1051,22 @@ mod tests {
             .expect("failed to set a bonding data value");
         accessor_proxy.commit().expect("failed to commit a bonding data value");
 
-        // The stash should fail to initialize.
-        let stash_new_future = Stash::new(accessor_proxy, inspect);
-        assert!(exec.run_singlethreaded(stash_new_future).is_err());
+        // The stash should still initialize, skipping the unreadable entry.
+        let stash_new_future = Stash::new(accessor_proxy, inspect, None);
+        let stash =
+            exec.run_singlethreaded(stash_new_future).expect("expected Stash to initialize");
+        assert!(stash.bonding_data.is_empty());
+        assert_eq!(stash.load_report().bonds_skipped, 1);
     }
 
     #[test]
-    fn new_stash_fails_with_malformed_json() {
+    fn new_stash_skips_malformed_json() {
         let mut exec = fasync::Executor::new().expect("failed to create an executor");
 
         let inspect = fuchsia_inspect::Inspector::new().root().create_child("test");
 
         // Create a mock Stash service interface.
-        let accessor_proxy = create_stash_accessor("new_stash_fails_with_malformed_json")
+        let accessor_proxy = create_stash_accessor("new_stash_skips_malformed_json")
             .expect("failed to create StashAccessor");
 
         // Set a vector that contains a malformed JSON value
@@ -328,9 +1075,12 @@ mod tests {
             .expect("failed to set a bonding data value");
         accessor_proxy.commit().expect("failed to commit a bonding data value");
 
-        // The stash should fail to initialize.
-        let stash_new_future = Stash::new(accessor_proxy, inspect);
-        assert!(exec.run_singlethreaded(stash_new_future).is_err());
+        // The stash should still initialize, skipping the unreadable entry.
+        let stash_new_future = Stash::new(accessor_proxy, inspect, None);
+        let stash =
+            exec.run_singlethreaded(stash_new_future).expect("expected Stash to initialize");
+        assert!(stash.bonding_data.is_empty());
+        assert_eq!(stash.load_report().bonds_skipped, 1);
     }
 
     #[test]
@@ -395,7 +1145,7 @@ mod tests {
         accessor_proxy.commit().expect("failed to commit bonding data values");
 
         // The stash should initialize with bonding data stored in stash
-        let stash_new_future = Stash::new(accessor_proxy, inspect);
+        let stash_new_future = Stash::new(accessor_proxy, inspect, None);
         let stash = exec.run_singlethreaded(stash_new_future).expect("stash failed to initialize");
 
         // There should be devices registered for two local addresses.
@@ -456,7 +1206,7 @@ mod tests {
         let accessor_proxy = create_stash_accessor("store_bond_commits_entry")
             .expect("failed to create StashAccessor");
         let mut stash = exec
-            .run_singlethreaded(Stash::new(accessor_proxy.clone(), inspect))
+            .run_singlethreaded(Stash::new(accessor_proxy.clone(), inspect, None))
             .expect("stash failed to initialize");
 
         let bonding_data = BondingData {
@@ -483,7 +1233,9 @@ mod tests {
             bond
         );
 
-        // The new data should be accessible over FIDL.
+        // Writes are coalesced by a background task; flush to force them through before
+        // checking that the new data is accessible over FIDL.
+        exec.run_singlethreaded(stash.flush()).expect("failed to flush stash");
         assert_eq!(
             exec.run_singlethreaded(accessor_proxy.get_value("bonding-data:id-1"))
                 .expect("failed to get value")
@@ -539,7 +1291,7 @@ mod tests {
         accessor_proxy.commit().expect("failed to initialize bonding data for testing");
 
         let stash = exec
-            .run_singlethreaded(Stash::new(accessor_proxy, inspect))
+            .run_singlethreaded(Stash::new(accessor_proxy, inspect, None))
             .expect("stash failed to initialize");
 
         // Should return None for unknown address.
@@ -588,7 +1340,7 @@ mod tests {
         accessor_proxy.commit().expect("failed to initialize host data for testing");
 
         let stash = exec
-            .run_singlethreaded(Stash::new(accessor_proxy, inspect))
+            .run_singlethreaded(Stash::new(accessor_proxy, inspect, None))
             .expect("stash failed to initialize");
 
         // Should return None for unknown identity address.
@@ -653,7 +1405,7 @@ mod tests {
         accessor_proxy.commit().expect("failed to initialize bonding data for testing");
 
         let mut stash = exec
-            .run_singlethreaded(Stash::new(accessor_proxy, inspect))
+            .run_singlethreaded(Stash::new(accessor_proxy, inspect, None))
             .expect("stash failed to initialize");
 
         // OK to remove some unknown peer...
@@ -688,7 +1440,7 @@ mod tests {
         let accessor_proxy =
             create_stash_accessor("store_local_irk").expect("failed to create StashAccessor");
         let mut stash = exec
-            .run_singlethreaded(Stash::new(accessor_proxy.clone(), inspect))
+            .run_singlethreaded(Stash::new(accessor_proxy.clone(), inspect, None))
             .expect("stash failed to initialize");
 
         let host_data = HostData {
@@ -709,7 +1461,9 @@ mod tests {
             stash.host_data.get("00:00:00:00:00:01").unwrap()
         );
 
-        // The new data should be accessible over FIDL.
+        // Writes are coalesced by a background task; flush to force them through before
+        // checking that the new data is accessible over FIDL.
+        exec.run_singlethreaded(stash.flush()).expect("failed to flush stash");
         assert_eq!(
             exec.run_singlethreaded(accessor_proxy.get_value("host-data:00:00:00:00:00:01"))
                 .expect("failed to get value")
@@ -738,7 +1492,9 @@ mod tests {
             stash.host_data.get("00:00:00:00:00:01").unwrap()
         );
 
-        // The new data should be accessible over FIDL.
+        // Writes are coalesced by a background task; flush to force them through before
+        // checking that the new data is accessible over FIDL.
+        exec.run_singlethreaded(stash.flush()).expect("failed to flush stash");
         assert_eq!(
             exec.run_singlethreaded(accessor_proxy.get_value("host-data:00:00:00:00:00:01"))
                 .expect("failed to get value")
@@ -748,4 +1504,95 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn stash_crypto_seal_open_round_trip() {
+        let crypto = StashCrypto::from_passphrase(b"some passphrase", &[0u8; CRYPTO_SALT_LEN])
+            .expect("failed to derive stash crypto key");
+        let sealed = crypto.seal("super secret bonding data").expect("failed to seal");
+        assert_ne!(sealed, "super secret bonding data");
+        let opened = crypto.open(&sealed).expect("failed to open");
+        assert_eq!(opened, "super secret bonding data");
+    }
+
+    #[test]
+    fn stash_crypto_open_rejects_tampered_ciphertext() {
+        let crypto = StashCrypto::from_passphrase(b"some passphrase", &[0u8; CRYPTO_SALT_LEN])
+            .expect("failed to derive stash crypto key");
+        let mut sealed = base64::decode(&crypto.seal("secret").expect("failed to seal")).unwrap();
+        *sealed.last_mut().unwrap() ^= 0xff;
+        assert!(crypto.open(&base64::encode(&sealed)).is_err());
+    }
+
+    #[test]
+    fn new_stash_quarantines_checksum_mismatch() {
+        let mut exec = fasync::Executor::new().expect("failed to create an executor");
+        let inspect = fuchsia_inspect::Inspector::new().root().create_child("test");
+        let accessor_proxy = create_stash_accessor("new_stash_quarantines_checksum_mismatch")
+            .expect("failed to create StashAccessor");
+
+        let json = r#"
+            {
+               "identifier": "id-1",
+               "localAddress": "00:00:00:00:00:01",
+               "name": null,
+               "le": null,
+               "bredr": null
+            }"#
+        .to_string();
+        accessor_proxy
+            .set_value("bonding-data:id-1", &mut Value::Stringval(json))
+            .expect("failed to set value");
+        // A checksum that doesn't match the value above.
+        accessor_proxy
+            .set_value("checksum:bonding-data:id-1", &mut Value::Stringval(checksum("garbage")))
+            .expect("failed to set checksum");
+        accessor_proxy.commit().expect("failed to initialize bonding data for testing");
+
+        let stash_new_future = Stash::new(accessor_proxy.clone(), inspect, None);
+        let stash =
+            exec.run_singlethreaded(stash_new_future).expect("expected Stash to initialize");
+
+        // The corrupt entry is skipped, not loaded.
+        assert!(stash.bonding_data.is_empty());
+        assert_eq!(stash.load_report().bonds_skipped, 1);
+
+        // It's quarantined under CORRUPT_BONDING_DATA_PREFIX, and removed from its original key
+        // and checksum so it isn't retried on the next load.
+        assert!(exec
+            .run_singlethreaded(accessor_proxy.get_value("corrupt-bonding-data:bonding-data:id-1"))
+            .expect("failed to get value")
+            .is_some());
+        assert!(exec
+            .run_singlethreaded(accessor_proxy.get_value("bonding-data:id-1"))
+            .expect("failed to get value")
+            .is_none());
+        assert!(exec
+            .run_singlethreaded(accessor_proxy.get_value("checksum:bonding-data:id-1"))
+            .expect("failed to get value")
+            .is_none());
+    }
+
+    #[test]
+    fn ah_known_vector_produces_expected_hash() {
+        // `resolve_peer_address` builds directly on `ah`; `LeData`'s exact fields aren't
+        // available in this checkout (defined in the external `fuchsia_bluetooth` crate), so this
+        // exercises the IRK/RPA hashing primitive itself rather than the full bond-lookup path.
+        //
+        // Bluetooth Core Spec, Vol 3, Part H, Appendix D.7: IRK
+        // ec0234a357c8ad05341010a60a397d9b, prand 708194, hash 0dfbaa. Asserting against this
+        // published vector (rather than only self-consistency) catches a future byte-order
+        // regression in `ah` that a same-input/different-input comparison alone would miss.
+        let irk: [u8; 16] = [
+            0xec, 0x02, 0x34, 0xa3, 0x57, 0xc8, 0xad, 0x05, 0x34, 0x10, 0x10, 0xa6, 0x0a, 0x39,
+            0x7d, 0x9b,
+        ];
+        let prand = [0x70, 0x81, 0x94];
+        assert_eq!(ah(&irk, &prand), [0x0d, 0xfb, 0xaa]);
+
+        // `ah` is deterministic: hashing the same IRK/prand pair twice must agree, and a
+        // different prand must (overwhelmingly likely) produce a different hash.
+        assert_eq!(ah(&irk, &prand), ah(&irk, &prand));
+        assert_ne!(ah(&irk, &prand), ah(&irk, &[0x70, 0x81, 0x95]));
+    }
 }
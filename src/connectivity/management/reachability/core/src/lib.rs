@@ -6,10 +6,31 @@ extern crate log;
 use failure::Error;
 use fidl_fuchsia_net_stack as stack;
 use fidl_fuchsia_netstack as netstack;
+use fuchsia_async::{self as fasync, TimeoutExt as _};
+use fuchsia_zircon as zx;
+use futures::channel::mpsc;
+use futures::FutureExt as _;
+use hyper::{Body, Method, Request};
 use network_manager_core::error;
 use network_manager_core::hal;
 use network_manager_core::lifmgr::{subnet_mask_to_prefix_length, to_ip_addr, LifIpAddr};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+
+/// Default URL used to actively probe for full Internet connectivity once a gateway has been
+/// found. Mirrors the "generate_204" style endpoints used by captive portal detectors: a
+/// reachable, unfiltered path returns the expected status with an empty body, while a captive
+/// portal intercepts it with a 2xx/3xx redirect or interstitial page instead.
+const DEFAULT_REACHABILITY_URL: &str = "http://www.gstatic.com/generate_204";
+const DEFAULT_REACHABILITY_EXPECTED_STATUS: u16 = 204;
+/// How long to wait for a single reachability probe attempt's response before giving up on that
+/// attempt. See `DEFAULT_REACHABILITY_PROBE_RETRIES`.
+const REACHABILITY_PROBE_TIMEOUT: zx::Duration = zx::Duration::from_seconds(5);
+/// Default number of times a failed reachability probe is retried before `State::Gateway` is
+/// reported, and the default delay between retries.
+const DEFAULT_REACHABILITY_PROBE_RETRIES: u8 = 2;
+const DEFAULT_REACHABILITY_PROBE_BACKOFF: zx::Duration = zx::Duration::from_millis(500);
+/// Maximum number of transitions retained per interface in `Monitor::history`.
+const HISTORY_LEN: usize = 32;
 
 /// `Stats` keeps the monitoring service statistic counters.
 #[derive(Debug, Default, Clone, Copy)]
@@ -84,11 +105,53 @@ pub struct ReachabilityInfo {
 type Id = hal::PortId;
 type StateInfo = HashMap<Id, ReachabilityInfo>;
 
+/// `StateEvent` records one observed reachability-state transition for an interface. See
+/// `Monitor::history` and `Monitor::subscribe`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateEvent {
+    /// Time the transition was observed.
+    pub timestamp: zx::Time,
+    /// IPv4 state before the transition; `None` on the interface's first observation.
+    pub previous_v4: Option<State>,
+    /// IPv4 state after the transition.
+    pub new_v4: State,
+    /// IPv6 state before the transition; `None` on the interface's first observation.
+    pub previous_v6: Option<State>,
+    /// IPv6 state after the transition.
+    pub new_v6: State,
+}
+
 /// `Monitor` monitors the reachability state.
 pub struct Monitor {
     hal: hal::NetCfg,
     state_info: StateInfo,
     stats: Stats,
+    /// URL probed once a gateway is found, to determine whether `State::Internet` or
+    /// `State::WalledGarden` should be reported. See `set_reachability_test`.
+    reachability_url: String,
+    /// HTTP status expected from `reachability_url` on an unfiltered path to the Internet.
+    reachability_expected_status: u16,
+    /// Number of times a failed reachability probe is retried (after the first attempt) before
+    /// giving up and reporting `State::Gateway`. See `set_reachability_probe_backoff`.
+    reachability_probe_retries: u8,
+    /// Delay between reachability probe retries.
+    reachability_probe_backoff: zx::Duration,
+    /// Last observed rx packet counter per interface, used by `packet_count_increases` to
+    /// detect whether traffic is actually flowing. See `sample_packet_counters`.
+    packet_counters: HashMap<Id, PacketCounterSample>,
+    /// Bounded per-interface history of observed state transitions. See `Monitor::history`.
+    history: HashMap<Id, VecDeque<StateEvent>>,
+    /// Subscribers registered via `Monitor::subscribe`, notified of every transition. Closed
+    /// receivers are pruned the next time a transition is published.
+    subscribers: Vec<mpsc::UnboundedSender<(Id, StateEvent)>>,
+}
+
+/// Packet-counter sample for an interface, used to detect whether traffic is flowing before
+/// advancing past `State::Up`. See `packet_count_increases`.
+#[derive(Debug, Clone, Copy)]
+struct PacketCounterSample {
+    rx_packets: u64,
+    sampled_at: zx::Time,
 }
 
 #[derive(Debug)]
@@ -102,7 +165,36 @@ impl Monitor {
     /// Create the monitoring service.
     pub fn new() -> Result<Self, Error> {
         let hal = hal::NetCfg::new()?;
-        Ok(Monitor { hal, state_info: HashMap::new(), stats: Default::default() })
+        Ok(Monitor {
+            hal,
+            state_info: HashMap::new(),
+            stats: Default::default(),
+            reachability_url: DEFAULT_REACHABILITY_URL.to_string(),
+            reachability_expected_status: DEFAULT_REACHABILITY_EXPECTED_STATUS,
+            reachability_probe_retries: DEFAULT_REACHABILITY_PROBE_RETRIES,
+            reachability_probe_backoff: DEFAULT_REACHABILITY_PROBE_BACKOFF,
+            packet_counters: HashMap::new(),
+            history: HashMap::new(),
+            subscribers: Vec::new(),
+        })
+    }
+
+    /// `set_reachability_test` configures the URL and expected HTTP status used to actively
+    /// probe for Internet connectivity once a gateway has been found. Defaults to a
+    /// "generate_204"-style endpoint; callers can point this at another reachability-test
+    /// endpoint (e.g. for testing, or for deployments behind a different captive portal
+    /// detector).
+    pub fn set_reachability_test(&mut self, url: String, expected_status: u16) {
+        self.reachability_url = url;
+        self.reachability_expected_status = expected_status;
+    }
+
+    /// `set_reachability_probe_backoff` configures how many times a failed reachability probe
+    /// is retried, and the delay between retries, before `probe_internet_reachable` gives up and
+    /// leaves the state at `State::Gateway`.
+    pub fn set_reachability_probe_backoff(&mut self, retries: u8, backoff: zx::Duration) {
+        self.reachability_probe_retries = retries;
+        self.reachability_probe_backoff = backoff;
     }
 
     /// `stats` returns monitoring service statistic counters.
@@ -114,6 +206,32 @@ impl Monitor {
         &self.state_info
     }
 
+    /// `history` returns the bounded history of reachability-state transitions observed for
+    /// interface `id`, oldest first. Empty if no transition has been observed yet.
+    pub fn history(&self, id: Id) -> Vec<StateEvent> {
+        self.history.get(&id).map(|h| h.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    /// `subscribe` registers a new subscriber and returns a stream of `(Id, StateEvent)`
+    /// delivered as transitions are observed. Multiple subscribers may be registered; a
+    /// subscriber that drops its receiver is pruned the next time a transition is published.
+    pub fn subscribe(&mut self) -> mpsc::UnboundedReceiver<(Id, StateEvent)> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    /// `publish` records `event` in `id`'s bounded history and notifies subscribers.
+    fn publish(&mut self, id: Id, event: StateEvent) {
+        let history = self.history.entry(id).or_insert_with(VecDeque::new);
+        history.push_back(event.clone());
+        if history.len() > HISTORY_LEN {
+            history.pop_front();
+        }
+        self.subscribers
+            .retain(|subscriber| subscriber.unbounded_send((id, event.clone())).is_ok());
+    }
+
     fn dump_state(&self) {
         for (key, value) in &self.state_info {
             debug!("{:?}: {:?}", key, value);
@@ -124,6 +242,79 @@ impl Monitor {
         warn!("State Change {:?}: {:?}", id, info);
     }
 
+    /// `probe_internet_reachable` runs `try_probe_internet_reachable` up to
+    /// `reachability_probe_retries + 1` times, waiting `reachability_probe_backoff` between
+    /// attempts, so a single dropped packet or transient timeout doesn't demote a good link.
+    /// Retries stop as soon as an attempt yields a definitive answer (`State::Internet` or
+    /// `State::WalledGarden`); a probe that never gets one leaves the state at `State::Gateway`.
+    async fn probe_internet_reachable(&self) -> State {
+        for attempt in 0..=self.reachability_probe_retries {
+            if attempt > 0 {
+                fasync::Timer::new(self.reachability_probe_backoff.after_now()).await;
+            }
+            match self.try_probe_internet_reachable().await {
+                State::Gateway => continue,
+                state => return state,
+            }
+        }
+        State::Gateway
+    }
+
+    /// `try_probe_internet_reachable` issues a single HTTP GET to `reachability_url` and
+    /// classifies the result: a response matching `reachability_expected_status` indicates an
+    /// unfiltered path to the Internet (`State::Internet`); any other 2xx/3xx response indicates
+    /// something intercepted the request, e.g. a captive portal (`State::WalledGarden`); a
+    /// timeout or connection failure means connectivity past the gateway could not be confirmed
+    /// by this attempt, so the state remains `State::Gateway`.
+    async fn try_probe_internet_reachable(&self) -> State {
+        let uri = match self.reachability_url.parse() {
+            Ok(uri) => uri,
+            Err(e) => {
+                warn!("invalid reachability test url {}: {:?}", self.reachability_url, e);
+                return State::Gateway;
+            }
+        };
+        let request = match Request::builder().method(Method::GET).uri(uri).body(Body::empty()) {
+            Ok(request) => request,
+            Err(e) => {
+                warn!("failed to build reachability probe request: {:?}", e);
+                return State::Gateway;
+            }
+        };
+
+        let client = fuchsia_hyper::new_client();
+        let response = client
+            .request(request)
+            .map(Some)
+            .on_timeout(REACHABILITY_PROBE_TIMEOUT.after_now(), || None)
+            .await;
+
+        match response {
+            Some(Ok(response))
+                if response.status().as_u16() == self.reachability_expected_status =>
+            {
+                State::Internet
+            }
+            Some(Ok(response))
+                if response.status().is_success() || response.status().is_redirection() =>
+            {
+                State::WalledGarden
+            }
+            Some(Ok(response)) => {
+                debug!("reachability probe got unexpected status {:?}", response.status());
+                State::Gateway
+            }
+            Some(Err(e)) => {
+                debug!("reachability probe failed: {:?}", e);
+                State::Gateway
+            }
+            None => {
+                debug!("reachability probe to {} timed out", self.reachability_url);
+                State::Gateway
+            }
+        }
+    }
+
     /// Returns the underlying event streams associated with the open channels to fuchsia.net.stack
     /// and fuchsia.netstack.
     pub fn take_event_streams(
@@ -132,6 +323,24 @@ impl Monitor {
         self.hal.take_event_streams()
     }
 
+    /// `sample_packet_counters` fetches the interface's current rx packet counter from the
+    /// netstack and caches it, returning the previous and current samples so the caller can
+    /// decide whether traffic has been seen. Returns `None` for a sample that isn't available
+    /// yet (e.g. the interface just appeared).
+    async fn sample_packet_counters(
+        &mut self,
+        id: Id,
+    ) -> (Option<PacketCounterSample>, Option<PacketCounterSample>) {
+        let previous = self.packet_counters.get(&id).copied();
+        let current = self.hal.get_interface_rx_packets(id).await.map(|rx_packets| {
+            PacketCounterSample { rx_packets, sampled_at: zx::Time::get_monotonic() }
+        });
+        if let Some(current) = current {
+            self.packet_counters.insert(id, current);
+        }
+        (previous, current)
+    }
+
     /// `update_states` processes an event and updates the reachability state accordingly.
     async fn update_state(&mut self, event: Event, interface_info: &hal::Interface) {
         let port_type = port_type(interface_info);
@@ -141,19 +350,40 @@ impl Monitor {
 
         debug!("update_state ->  event: {:?}, interface_info: {:?}", event, interface_info);
         let routes = self.hal.routes().await;
-        if let Some(new_info) = compute_state(&event, interface_info, routes) {
-            if let Some(info) = self.state_info.get(&interface_info.id) {
-                if info == &new_info {
-                    // State has not changed, nothing to do.
-                    debug!("update_state ->  no change");
-                    return;
-                }
+        let neighbors = self.hal.neighbors().await;
+        let (previous_counters, current_counters) =
+            self.sample_packet_counters(interface_info.id).await;
+        let had_traffic = packet_count_increases(previous_counters, current_counters);
+        if let Some(mut new_info) =
+            compute_state(&event, interface_info, routes, &neighbors, had_traffic)
+        {
+            if new_info.v4.state == State::Gateway {
+                new_info.v4.state = self.probe_internet_reachable().await;
+            }
+            if new_info.v6.state == State::Gateway {
+                new_info.v6.state = self.probe_internet_reachable().await;
+            }
+
+            let previous = self.state_info.get(&interface_info.id);
+            if previous == Some(&new_info) {
+                // State has not changed, nothing to do.
+                debug!("update_state ->  no change");
+                return;
             }
 
+            let event = StateEvent {
+                timestamp: zx::Time::get_monotonic(),
+                previous_v4: previous.map(|i| i.v4.state),
+                new_v4: new_info.v4.state,
+                previous_v6: previous.map(|i| i.v6.state),
+                new_v6: new_info.v6.state,
+            };
+
             self.report(interface_info.id, &new_info);
             self.stats.state_updates += 1;
             debug!("update_state ->  new state {:?}", new_info);
             self.state_info.insert(interface_info.id, new_info);
+            self.publish(interface_info.id, event);
         };
     }
 
@@ -211,6 +441,8 @@ fn compute_state(
     event: &Event,
     interface_info: &hal::Interface,
     routes: Option<Vec<hal::Route>>,
+    neighbors: &[hal::Neighbor],
+    had_traffic: bool,
 ) -> Option<ReachabilityInfo> {
     let port_type = port_type(interface_info);
     if port_type == PortType::Loopback {
@@ -226,6 +458,7 @@ fn compute_state(
     };
 
     let ipv4_address = ipv4_to_cidr(i.addr, i.netmask);
+    let ipv6_addresses = global_ipv6_addrs(&i.ipv6addrs);
 
     let mut new_info = ReachabilityInfo {
         port_type,
@@ -234,7 +467,11 @@ fn compute_state(
             is_l3: (i.flags & netstack::NET_INTERFACE_FLAG_DHCP) != 0 || ipv4_address.is_some(),
             state: State::Down,
         },
-        v6: NetworkInfo { is_default: false, is_l3: !i.ipv6addrs.is_empty(), state: State::Down },
+        v6: NetworkInfo {
+            is_default: false,
+            is_l3: !ipv6_addresses.is_empty(),
+            state: State::Down,
+        },
     };
 
     let is_up = (i.flags & netstack::NET_INTERFACE_FLAG_UP) != 0;
@@ -246,8 +483,7 @@ fn compute_state(
     new_info.v6.state = State::Up;
 
     // packet reception is network layer independent.
-    if !packet_count_increases(interface_info.id) {
-        // TODO(dpradilla): add active probing here.
+    if !had_traffic {
         // No packets seen, but interface is up.
         return Some(new_info);
     }
@@ -255,9 +491,15 @@ fn compute_state(
     new_info.v4.state = State::LinkLayerUp;
     new_info.v6.state = State::LinkLayerUp;
 
-    new_info.v4.state = network_layer_state(ipv4_address.into_iter(), &routes, &new_info.v4);
+    let (v4_state, v4_is_default) =
+        network_layer_state(ipv4_address.into_iter(), &routes, neighbors, &new_info.v4);
+    new_info.v4.state = v4_state;
+    new_info.v4.is_default = v4_is_default;
 
-    // TODO(dpradilla): Add support for IPV6
+    let (v6_state, v6_is_default) =
+        network_layer_state(ipv6_addresses.into_iter(), &routes, neighbors, &new_info.v6);
+    new_info.v6.state = v6_state;
+    new_info.v6.is_default = v6_is_default;
 
     Some(new_info)
 }
@@ -274,6 +516,31 @@ fn ipv4_to_cidr(
     }
 }
 
+// `global_ipv6_addrs` converts an interface's configured IPv6 addresses to `LifIpAddr`,
+// filtering out link-local (fe80::/10) addresses: those are always present on an up interface
+// and are not usable for off-link (gateway/Internet) reachability, so they should not count
+// towards L3 configuration or be used to match routes.
+fn global_ipv6_addrs(ipv6addrs: &[fidl_fuchsia_net::Subnet]) -> Vec<LifIpAddr> {
+    ipv6addrs
+        .iter()
+        .filter_map(|subnet| {
+            let address = to_ip_addr(subnet.addr);
+            if is_ipv6_link_local(&address) {
+                None
+            } else {
+                Some(LifIpAddr { address, prefix: subnet.prefix_len })
+            }
+        })
+        .collect()
+}
+
+fn is_ipv6_link_local(address: &std::net::IpAddr) -> bool {
+    match address {
+        std::net::IpAddr::V6(address) => (address.segments()[0] & 0xffc0) == 0xfe80,
+        std::net::IpAddr::V4(_) => false,
+    }
+}
+
 // `local_routes` traverses `route_table` to find routes that use a gateway local to `address`
 // network.
 fn local_routes<'a>(address: &LifIpAddr, route_table: &'a [hal::Route]) -> Vec<&'a hal::Route> {
@@ -287,16 +554,26 @@ fn local_routes<'a>(address: &LifIpAddr, route_table: &'a [hal::Route]) -> Vec<&
     local_routes
 }
 
-// TODO(dpradilla): implement.
-// `has_local_neighbors` checks for local neighbors.
-fn has_local_neighbors() -> bool {
-    true
+// `has_local_neighbors` checks that `neighbors` contains a reachable (neither stale nor
+// incomplete) entry for `gateway`, so a configured default route is not trusted until the
+// gateway has actually been confirmed present on the link.
+fn has_local_neighbors(gateway: &std::net::IpAddr, neighbors: &[hal::Neighbor]) -> bool {
+    neighbors.iter().any(|n| &n.ip == gateway && n.state == hal::NeighborState::Reachable)
 }
 
-// TODO(dpradilla): implement.
-// `packet_count_increases` verifies packet counts are going up.
-fn packet_count_increases(_: hal::PortId) -> bool {
-    true
+// `packet_count_increases` compares a freshly sampled rx packet counter against the last one
+// observed for an interface. Returns `true` only when the counter has changed since the last
+// sample (any change, up or down, means packets were processed; a decrease indicates the
+// counter wrapped or was reset), and `false` when there is no prior sample to compare against
+// yet.
+fn packet_count_increases(
+    previous: Option<PacketCounterSample>,
+    current: Option<PacketCounterSample>,
+) -> bool {
+    match (previous, current) {
+        (Some(previous), Some(current)) => current.rx_packets != previous.rx_packets,
+        _ => false,
+    }
 }
 
 fn port_type(interface_info: &hal::Interface) -> PortType {
@@ -311,47 +588,61 @@ fn port_type(interface_info: &hal::Interface) -> PortType {
     }
 }
 
-// `network_layer_state` determines the L3 reachability state.
+// `network_layer_state` determines the L3 reachability state and whether the default route for
+// this address family runs through this interface. It is address-family agnostic: called once
+// with the interface's IPv4 address and once with its (already link-local-filtered) IPv6
+// addresses, so v4 and v6 reachability are tracked independently for dual-stack interfaces.
 fn network_layer_state<'a>(
     mut addresses: impl Iterator<Item = LifIpAddr>,
     routes: &Option<Vec<hal::Route>>,
+    neighbors: &[hal::Neighbor],
     info: &NetworkInfo,
-) -> State {
+) -> (State, bool) {
     // This interface is not configured for L3, Nothing to check.
     if !info.is_l3 {
-        return info.state;
+        return (info.state, info.is_default);
     }
 
-    if info.state != State::LinkLayerUp || !has_local_neighbors() {
-        return info.state;
+    if info.state != State::LinkLayerUp {
+        return (info.state, info.is_default);
     }
 
     // TODO(dpradilla): add support for multiple addresses.
     let address = addresses.next();
     if address.is_none() {
-        return info.state;
+        return (info.state, info.is_default);
     }
 
     let mut new_state = State::Local;
 
     let route_table = match routes {
         Some(r) => r,
-        _ => return new_state,
+        // No routes known for this family, e.g. a SLAAC address with no router-advertised
+        // default route yet: the address is usable on-link, but there is no default route.
+        _ => return (new_state, false),
     };
 
     // Has local gateway.
     let gw = local_routes(&address.unwrap(), &route_table);
-    if gw.is_empty() {
-        return new_state;
+    let is_default = gw.iter().any(|r| r.target.prefix == 0);
+    let gateway = match gw.first().and_then(|r| r.gateway) {
+        Some(gateway) => gateway,
+        None => return (new_state, is_default),
+    };
+
+    // Only trust the gateway once it has a reachable neighbor-table entry; otherwise it is
+    // configured but not yet confirmed present on the link.
+    if !has_local_neighbors(&gateway, neighbors) {
+        return (new_state, is_default);
     }
 
-    // TODO(dpradilla): verify local gateways are reachable
     new_state = State::Gateway;
 
-    // TODO(dpradilla) Check for internet connectivity and set new_state =State::Internet on
-    // success.
+    // Active Internet-reachability probing (promoting Gateway to Internet/WalledGarden) is done
+    // by `Monitor::update_state` via `probe_internet_reachable`, after this returns and before
+    // the resulting state is recorded or published.
 
-    new_state
+    (new_state, is_default)
 }
 
 #[cfg(test)]
@@ -361,12 +652,51 @@ mod tests {
 
     #[test]
     fn test_has_local_neighbors() {
-        assert_eq!(has_local_neighbors(), true);
+        let neighbors = vec![
+            hal::Neighbor { ip: "1.2.3.1".parse().unwrap(), state: hal::NeighborState::Reachable },
+            hal::Neighbor { ip: "1.2.3.2".parse().unwrap(), state: hal::NeighborState::Incomplete },
+        ];
+
+        assert_eq!(
+            has_local_neighbors(&"1.2.3.1".parse().unwrap(), &neighbors),
+            true,
+            "reachable entry present"
+        );
+        assert_eq!(
+            has_local_neighbors(&"1.2.3.2".parse().unwrap(), &neighbors),
+            false,
+            "entry present but incomplete"
+        );
+        assert_eq!(
+            has_local_neighbors(&"1.2.3.3".parse().unwrap(), &neighbors),
+            false,
+            "no entry for gateway"
+        );
     }
 
     #[test]
     fn test_packet_count_increases() {
-        assert_eq!(packet_count_increases(hal::PortId::from(1)), true);
+        let sample =
+            |rx_packets| PacketCounterSample { rx_packets, sampled_at: zx::Time::from_nanos(0) };
+
+        assert_eq!(packet_count_increases(None, None), false, "no samples yet");
+        assert_eq!(packet_count_increases(None, Some(sample(10))), false, "no prior sample");
+        assert_eq!(packet_count_increases(Some(sample(10)), None), false, "no current sample");
+        assert_eq!(
+            packet_count_increases(Some(sample(10)), Some(sample(10))),
+            false,
+            "no new packets"
+        );
+        assert_eq!(
+            packet_count_increases(Some(sample(10)), Some(sample(20))),
+            true,
+            "rx counter increased"
+        );
+        assert_eq!(
+            packet_count_increases(Some(sample(u64::max_value())), Some(sample(5))),
+            true,
+            "rx counter wrapped around"
+        );
     }
 
     #[test]
@@ -507,13 +837,35 @@ mod tests {
             },
         ];
 
+        let reachable_gateway = vec![hal::Neighbor {
+            ip: "1.2.3.1".parse().unwrap(),
+            state: hal::NeighborState::Reachable,
+        }];
+
+        assert_eq!(
+            network_layer_state(
+                address.into_iter(),
+                &Some(vec![hal::Route {
+                    gateway: Some("1.2.3.1".parse().unwrap()),
+                    metric: None,
+                    port_id: Some(hal::PortId::from(1)),
+                    target: LifIpAddr { address: "0.0.0.0".parse().unwrap(), prefix: 0 },
+                }]),
+                &[],
+                &NetworkInfo { is_default: false, is_l3: true, state: State::LinkLayerUp },
+            ),
+            (State::Local, true),
+            "gateway configured but not yet confirmed reachable"
+        );
+
         assert_eq!(
             network_layer_state(
                 address.into_iter(),
                 &Some(route_table),
+                &reachable_gateway,
                 &NetworkInfo { is_default: false, is_l3: true, state: State::LinkLayerUp },
             ),
-            State::Gateway,
+            (State::Gateway, true),
             "All is good"
         );
 
@@ -521,9 +873,10 @@ mod tests {
             network_layer_state(
                 address.into_iter(),
                 &None,
+                &reachable_gateway,
                 &NetworkInfo { is_default: false, is_l3: true, state: State::LinkLayerUp }
             ),
-            State::Local,
+            (State::Local, false),
             "No routes"
         );
 
@@ -531,9 +884,10 @@ mod tests {
             network_layer_state(
                 None.into_iter(),
                 &Some(route_table_2),
+                &reachable_gateway,
                 &NetworkInfo { is_default: false, is_l3: true, state: State::NetworkLayerUp }
             ),
-            State::NetworkLayerUp,
+            (State::NetworkLayerUp, false),
             "default route is not local"
         );
 
@@ -553,6 +907,8 @@ mod tests {
                 dhcp_client_enabled: false,
             },
             None,
+            &[],
+            true,
         );
         assert_eq!(got, None, "not and ethernet interface");
 
@@ -566,6 +922,8 @@ mod tests {
                 dhcp_client_enabled: false,
             },
             None,
+            &[],
+            true,
         );
         assert_eq!(got, None, "ethernet interface, but not a valid event");
 
@@ -590,6 +948,8 @@ mod tests {
                 dhcp_client_enabled: false,
             },
             None,
+            &[],
+            true,
         );
         let want = Some(ReachabilityInfo {
             port_type: PortType::Ethernet,
@@ -619,6 +979,8 @@ mod tests {
                 dhcp_client_enabled: false,
             },
             None,
+            &[],
+            true,
         );
         let want = Some(ReachabilityInfo {
             port_type: PortType::Ethernet,
@@ -653,6 +1015,8 @@ mod tests {
                 port_id: Some(hal::PortId::from(1)),
                 target: LifIpAddr { address: "0.0.0.0".parse().unwrap(), prefix: 0 },
             }]),
+            &[],
+            true,
         );
         let want = Some(ReachabilityInfo {
             port_type: PortType::Ethernet,
@@ -690,10 +1054,15 @@ mod tests {
                 port_id: Some(hal::PortId::from(1)),
                 target: LifIpAddr { address: "0.0.0.0".parse().unwrap(), prefix: 0 },
             }]),
+            &[hal::Neighbor {
+                ip: "1.2.3.1".parse().unwrap(),
+                state: hal::NeighborState::Reachable,
+            }],
+            true,
         );
         let want = Some(ReachabilityInfo {
             port_type: PortType::Ethernet,
-            v4: NetworkInfo { is_default: false, is_l3: true, state: State::Gateway },
+            v4: NetworkInfo { is_default: true, is_l3: true, state: State::Gateway },
             v6: NetworkInfo { is_default: false, is_l3: false, state: State::LinkLayerUp },
         });
         assert_eq!(
@@ -727,6 +1096,8 @@ mod tests {
                 port_id: Some(hal::PortId::from(1)),
                 target: LifIpAddr { address: "::".parse().unwrap(), prefix: 0 },
             }]),
+            &[],
+            true,
         );
         let want = Some(ReachabilityInfo {
             port_type: PortType::Ethernet,
@@ -738,6 +1109,104 @@ mod tests {
             "ethernet interface, ipv4 configured, interface up, no local gateway"
         );
 
+        let got = compute_state(
+            &Event::NetStack(fidl_fuchsia_netstack::NetInterface {
+                id: 1,
+                flags: netstack::NET_INTERFACE_FLAG_UP,
+                features: 0,
+                configuration: 0,
+                name: "eth0".to_string(),
+                addr: IpAddress("1.2.3.4".parse().unwrap()).into(),
+                netmask: IpAddress("255.255.255.0".parse().unwrap()).into(),
+                broadaddr: IpAddress("1.2.3.255".parse().unwrap()).into(),
+                ipv6addrs: vec![fidl_fuchsia_net::Subnet {
+                    addr: IpAddress("2001:db8::1".parse().unwrap()).into(),
+                    prefix_len: 64,
+                }],
+                hwaddr: vec![0, 0, 0, 0, 0, 0],
+            }),
+            &hal::Interface {
+                id: hal::PortId::from(1),
+                name: "ethernet/eth0".to_string(),
+                addr: None,
+                enabled: false,
+                dhcp_client_enabled: false,
+            },
+            Some(vec![hal::Route {
+                gateway: Some("2001:db8::2".parse().unwrap()),
+                metric: None,
+                port_id: Some(hal::PortId::from(1)),
+                target: LifIpAddr { address: "::".parse().unwrap(), prefix: 0 },
+            }]),
+            &[hal::Neighbor {
+                ip: "2001:db8::2".parse().unwrap(),
+                state: hal::NeighborState::Reachable,
+            }],
+            true,
+        );
+        let want = Some(ReachabilityInfo {
+            port_type: PortType::Ethernet,
+            v4: NetworkInfo { is_default: false, is_l3: true, state: State::Local },
+            v6: NetworkInfo { is_default: true, is_l3: true, state: State::Gateway },
+        });
+        assert_eq!(
+            got, want,
+            "ethernet interface, ipv6 global address configured, with local ipv6 gateway"
+        );
+
+        let got = compute_state(
+            &Event::NetStack(fidl_fuchsia_netstack::NetInterface {
+                id: 1,
+                flags: netstack::NET_INTERFACE_FLAG_UP,
+                features: 0,
+                configuration: 0,
+                name: "eth0".to_string(),
+                addr: IpAddress("1.2.3.4".parse().unwrap()).into(),
+                netmask: IpAddress("255.255.255.0".parse().unwrap()).into(),
+                broadaddr: IpAddress("1.2.3.255".parse().unwrap()).into(),
+                ipv6addrs: vec![fidl_fuchsia_net::Subnet {
+                    addr: IpAddress("2001:db8::1".parse().unwrap()).into(),
+                    prefix_len: 64,
+                }],
+                hwaddr: vec![0, 0, 0, 0, 0, 0],
+            }),
+            &hal::Interface {
+                id: hal::PortId::from(1),
+                name: "ethernet/eth0".to_string(),
+                addr: None,
+                enabled: false,
+                dhcp_client_enabled: false,
+            },
+            None,
+            &[],
+            true,
+        );
+        let want = Some(ReachabilityInfo {
+            port_type: PortType::Ethernet,
+            v4: NetworkInfo { is_default: false, is_l3: true, state: State::Local },
+            v6: NetworkInfo { is_default: false, is_l3: true, state: State::Local },
+        });
+        assert_eq!(
+            got, want,
+            "ethernet interface, ipv6 global address via slaac, no default route yet"
+        );
+
         // TODO(dpradilla): Add test cases to cover functionality that is not yet implemented.
     }
+
+    #[test]
+    fn test_global_ipv6_addrs() {
+        let addrs = vec![
+            fidl_fuchsia_net::Subnet {
+                addr: IpAddress("fe80::1".parse().unwrap()).into(),
+                prefix_len: 64,
+            },
+            fidl_fuchsia_net::Subnet {
+                addr: IpAddress("2001:db8::1".parse().unwrap()).into(),
+                prefix_len: 64,
+            },
+        ];
+        let want = vec![LifIpAddr { address: "2001:db8::1".parse().unwrap(), prefix: 64 }];
+        assert_eq!(global_ipv6_addrs(&addrs), want, "link-local address filtered out");
+    }
 }
@@ -4,12 +4,14 @@
 
 use {
     crate::{
-        buffer_reader::BufferReader, mac::MacAddr, mac::ReasonCode, organization::Oui,
-        unaligned_view::UnalignedView,
+        buffer_reader::BufferReader, buffer_writer::BufferWriter, mac::MacAddr, mac::ReasonCode,
+        organization::Oui, unaligned_view::UnalignedView,
     },
+    std::cmp::Ordering,
+    std::fmt,
     std::mem::size_of,
     wlan_bitfield::bitfield,
-    zerocopy::{AsBytes, ByteSlice, FromBytes, LayoutVerified, Unaligned},
+    zerocopy::{AsBytes, ByteSlice, ByteSliceMut, FromBytes, LayoutVerified, Unaligned},
 };
 
 macro_rules! pub_const {
@@ -489,6 +491,42 @@ pub struct MpmCloseView<B> {
     pub pmk: Option<LayoutVerified<B, MpmPmk>>,
 }
 
+// A HWMP sequence number (IEEE Std 802.11-2016, 14.9.2). These wrap modulo 2^32, so comparing
+// the inner `u32` directly breaks as soon as a value wraps past an older one; use
+// `is_newer_than`/`PartialOrd` instead, which implement the serial number arithmetic from
+// IEEE Std 802.11-2016, 14.9.2, footnote referencing RFC 1982.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, AsBytes, FromBytes, Unaligned)]
+pub struct HwmpSeqno(pub u32);
+
+impl HwmpSeqno {
+    /// `self` is newer than `other` iff the two differ and the forward distance from `other` to
+    /// `self` (mod 2^32) is less than half the sequence space. A distance of exactly 2^31 is
+    /// undefined by the spec and is treated as "not newer" in either direction.
+    pub fn is_newer_than(&self, other: &HwmpSeqno) -> bool {
+        self.0 != other.0 && self.0.wrapping_sub(other.0) < 0x8000_0000
+    }
+
+    /// Wrapping increment, used when originating a new PREQ/PREP/PERR.
+    pub fn increment(&self) -> HwmpSeqno {
+        HwmpSeqno(self.0.wrapping_add(1))
+    }
+}
+
+impl PartialOrd for HwmpSeqno {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.0 == other.0 {
+            Some(Ordering::Equal)
+        } else if self.is_newer_than(other) {
+            Some(Ordering::Greater)
+        } else if other.is_newer_than(self) {
+            Some(Ordering::Less)
+        } else {
+            None
+        }
+    }
+}
+
 // IEEE Std 802.11-2016, 9.4.2.113, Figure 9-478
 #[bitfield(
     0       gate_announcement,
@@ -513,7 +551,7 @@ pub struct PreqHeader {
     pub element_ttl: u8,
     pub path_discovery_id: u32,
     pub originator_addr: MacAddr,
-    pub originator_hwmp_seqno: u32,
+    pub originator_hwmp_seqno: HwmpSeqno,
 }
 
 // Fixed-length fields of the PREQ elements that follow the optional Originator External Address
@@ -545,7 +583,7 @@ pub struct PreqPerTargetFlags(pub u8);
 pub struct PreqPerTarget {
     pub flags: PreqPerTargetFlags,
     pub target_addr: MacAddr,
-    pub target_hwmp_seqno: u32,
+    pub target_hwmp_seqno: HwmpSeqno,
 }
 
 pub struct PreqView<B> {
@@ -575,7 +613,7 @@ pub struct PrepHeader {
     pub hop_count: u8,
     pub element_ttl: u8,
     pub target_addr: MacAddr,
-    pub target_hwmp_seqno: u32,
+    pub target_hwmp_seqno: HwmpSeqno,
 }
 
 // Fixed-length fields of the PREP element that follow
@@ -587,7 +625,7 @@ pub struct PrepTail {
     pub lifetime: u32,
     pub metric: u32,
     pub originator_addr: MacAddr,
-    pub originator_hwmp_seqno: u32,
+    pub originator_hwmp_seqno: HwmpSeqno,
 }
 
 pub struct PrepView<B> {
@@ -624,7 +662,7 @@ pub struct PerrDestinationFlags(pub u8);
 pub struct PerrDestinationHeader {
     pub flags: PerrDestinationFlags,
     pub dest_addr: MacAddr,
-    pub hwmp_seqno: u32,
+    pub hwmp_seqno: HwmpSeqno,
 }
 
 pub struct PerrDestinationView<B> {
@@ -692,6 +730,192 @@ impl<B: ByteSlice> PerrDestinationIter<B> {
     }
 }
 
+// Builders for the mesh elements above. Each `write_*` function emits exactly the bytes its
+// `*View` counterpart expects to parse back, including the same addr_ext-gated optional fields,
+// so that parse(write(x)) round-trips to x.
+
+/// Writes a Mesh Peering Open element: the fixed header followed by the PMK, if present.
+pub fn write_mpm_open<B: ByteSliceMut>(
+    w: &mut BufferWriter<B>,
+    header: &MpmHeader,
+    pmk: Option<&MpmPmk>,
+) -> Option<()> {
+    w.write_value(header)?;
+    if let Some(pmk) = pmk {
+        w.write_value(pmk)?;
+    }
+    Some(())
+}
+
+/// Writes a Mesh Peering Confirm element: the fixed header, the mandatory Peer Link ID, and the
+/// PMK, if present.
+pub fn write_mpm_confirm<B: ByteSliceMut>(
+    w: &mut BufferWriter<B>,
+    header: &MpmHeader,
+    peer_link_id: u16,
+    pmk: Option<&MpmPmk>,
+) -> Option<()> {
+    w.write_value(header)?;
+    w.write_value(&peer_link_id)?;
+    if let Some(pmk) = pmk {
+        w.write_value(pmk)?;
+    }
+    Some(())
+}
+
+/// Writes a Mesh Peering Close element: the fixed header, the Peer Link ID (omitted if the peer
+/// link was never established), the mandatory reason code, and the PMK, if present.
+pub fn write_mpm_close<B: ByteSliceMut>(
+    w: &mut BufferWriter<B>,
+    header: &MpmHeader,
+    peer_link_id: Option<u16>,
+    reason_code: ReasonCode,
+    pmk: Option<&MpmPmk>,
+) -> Option<()> {
+    w.write_value(header)?;
+    if let Some(peer_link_id) = peer_link_id {
+        w.write_value(&peer_link_id)?;
+    }
+    w.write_value(&reason_code)?;
+    if let Some(pmk) = pmk {
+        w.write_value(pmk)?;
+    }
+    Some(())
+}
+
+/// Writes a PREQ element: the fixed header, the Originator External Address (required iff
+/// `header.flags.addr_ext()` is set), the middle fields, and the per-target entries.
+///
+/// Returns `None`, writing nothing useful, if `header.flags.addr_ext()` is set but
+/// `originator_external_addr` is `None`, or if the buffer is too small.
+pub fn write_preq<B: ByteSliceMut>(
+    w: &mut BufferWriter<B>,
+    header: &PreqHeader,
+    originator_external_addr: Option<&MacAddr>,
+    middle: &PreqMiddle,
+    targets: &[PreqPerTarget],
+) -> Option<()> {
+    w.write_value(header)?;
+    if header.flags.addr_ext() {
+        w.write_value(originator_external_addr?)?;
+    }
+    w.write_value(middle)?;
+    for target in targets {
+        w.write_value(target)?;
+    }
+    Some(())
+}
+
+/// Writes a PREP element: the fixed header, the Target External Address (required iff
+/// `header.flags.addr_ext()` is set), and the tail fields.
+pub fn write_prep<B: ByteSliceMut>(
+    w: &mut BufferWriter<B>,
+    header: &PrepHeader,
+    target_external_addr: Option<&MacAddr>,
+    tail: &PrepTail,
+) -> Option<()> {
+    w.write_value(header)?;
+    if header.flags.addr_ext() {
+        w.write_value(target_external_addr?)?;
+    }
+    w.write_value(tail)?;
+    Some(())
+}
+
+/// One destination entry to write as part of a PERR element. Mirrors `PerrDestinationView`: the
+/// External Address is written iff `header.flags.addr_ext()` is set.
+pub struct PerrDestination<'a> {
+    pub header: PerrDestinationHeader,
+    pub ext_addr: Option<&'a MacAddr>,
+    pub reason_code: ReasonCode,
+}
+
+/// Writes a PERR element: the fixed header, with `num_destinations` derived from
+/// `destinations.len()`, followed by each destination's chunk.
+///
+/// Returns `None`, writing nothing useful, if there are more than 255 destinations, if some
+/// destination's `header.flags.addr_ext()` is set but its `ext_addr` is `None`, or if the buffer
+/// is too small.
+pub fn write_perr<B: ByteSliceMut>(
+    w: &mut BufferWriter<B>,
+    element_ttl: u8,
+    destinations: &[PerrDestination<'_>],
+) -> Option<()> {
+    if destinations.len() > u8::max_value() as usize {
+        return None;
+    }
+    w.write_value(&PerrHeader { element_ttl, num_destinations: destinations.len() as u8 })?;
+    for dest in destinations {
+        w.write_value(&dest.header)?;
+        if dest.header.flags.addr_ext() {
+            w.write_value(dest.ext_addr?)?;
+        }
+        w.write_value(&dest.reason_code)?;
+    }
+    Some(())
+}
+
+/// Incrementally builds a PERR destination list, writing each destination straight into the
+/// buffer as it's pushed instead of requiring the caller to assemble a `Vec<PerrDestination>` up
+/// front, for mesh stacks that discover unreachable destinations one at a time. Does not write
+/// the `PerrHeader` itself; the caller writes it after pushing, using `num_destinations()` for
+/// its `num_destinations` field.
+pub struct PerrDestinationListWriter<'a, B> {
+    writer: &'a mut BufferWriter<B>,
+    num_destinations: u8,
+}
+
+impl<'a, B: ByteSliceMut> PerrDestinationListWriter<'a, B> {
+    pub fn new(writer: &'a mut BufferWriter<B>) -> Self {
+        Self { writer, num_destinations: 0 }
+    }
+
+    /// Writes one destination's chunk: the fixed header, the External Address iff
+    /// `flags.addr_ext()` is set, and the reason code.
+    ///
+    /// Returns `None`, writing nothing for this destination, if `flags.addr_ext()` is set but
+    /// `ext_addr` is `None`, or if 255 destinations have already been pushed (`num_destinations` is
+    /// one byte). Also returns `None` if the buffer is too small; the part of the chunk written
+    /// before it ran out stays in the buffer, and `num_destinations` is not incremented.
+    pub fn push(
+        &mut self,
+        dest_addr: MacAddr,
+        flags: PerrDestinationFlags,
+        hwmp_seqno: HwmpSeqno,
+        ext_addr: Option<&MacAddr>,
+        reason_code: ReasonCode,
+    ) -> Option<()> {
+        if self.num_destinations == u8::max_value() {
+            return None;
+        }
+        if flags.addr_ext() && ext_addr.is_none() {
+            return None;
+        }
+        self.writer.write_value(&PerrDestinationHeader { flags, dest_addr, hwmp_seqno })?;
+        if flags.addr_ext() {
+            self.writer.write_value(ext_addr?)?;
+        }
+        self.writer.write_value(&reason_code)?;
+        self.num_destinations += 1;
+        Some(())
+    }
+
+    /// The number of destinations written so far.
+    pub fn num_destinations(&self) -> u8 {
+        self.num_destinations
+    }
+}
+
+/// OUI used by the Wi-Fi Alliance for P2P, MBO/OCE, and other post-802.11 extensions.
+pub const OUI_WFA: [u8; 3] = [0x50, 0x6f, 0x9a];
+/// Vendor IE type byte (within `OUI_WFA`) identifying a P2P IE. Wi-Fi P2P Technical
+/// Specification v1.7, 4.1.1.
+pub const WFA_TYPE_P2P: u8 = 0x09;
+/// Vendor IE type byte (within `OUI_WFA`) identifying an MBO/OCE IE.
+pub const WFA_TYPE_MBO_OCE: u8 = 0x16;
+/// Vendor IE type byte (within Microsoft's OUI, `00:50:F2`) identifying a WSC/WPS IE.
+pub const MSFT_TYPE_WSC: u8 = 0x04;
+
 // This enum represents all vendor IEs we know how to parse, plus an Unknown option for all other
 // vendor IEs.
 #[derive(Debug)]
@@ -699,10 +923,183 @@ pub enum VendorIe<B: ByteSlice> {
     // This does not contain the first byte of the IE body, since this byte identifies the IE as
     // WPA rather than another MSFT vendor IE.
     MsftLegacyWpa(B),
+    // OUI 00:50:F2, type MSFT_TYPE_WSC. Does not contain the type byte. See `WscAttributeListView`.
+    Wsc(B),
+    // OUI OUI_WFA, type WFA_TYPE_P2P. Does not contain the type byte. See `P2pAttributeListView`.
+    P2p(B),
+    // OUI OUI_WFA, type WFA_TYPE_MBO_OCE. Does not contain the type byte. See
+    // `MboAttributeListView`.
+    MboOce(B),
     // IEEE Std 802.11-2016, 9.4.2.26
     Unknown { oui: Oui, body: B },
 }
 
+impl<B: ByteSlice> VendorIe<B> {
+    /// Returns an iterator over this IE's P2P attributes if this is a `P2p` vendor IE.
+    pub fn p2p_attributes(self) -> Option<P2pAttributeListView<B>> {
+        match self {
+            VendorIe::P2p(body) => Some(P2pAttributeListView(body)),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over this IE's MBO/OCE attributes if this is an `MboOce` vendor IE.
+    pub fn mbo_oce_attributes(self) -> Option<MboAttributeListView<B>> {
+        match self {
+            VendorIe::MboOce(body) => Some(MboAttributeListView(body)),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over this IE's WSC attributes if this is a `Wsc` vendor IE.
+    pub fn wsc_attributes(self) -> Option<WscAttributeListView<B>> {
+        match self {
+            VendorIe::Wsc(body) => Some(WscAttributeListView(body)),
+            _ => None,
+        }
+    }
+}
+
+// P2P and MBO/OCE sub-elements share this TLV layout: a 1-byte attribute ID followed by a
+// 2-byte little-endian length and then the value. Each gets its own header/view/iterator types
+// below (rather than a single shared generic) since the two attribute ID spaces are unrelated
+// and may evolve independently.
+
+// Wi-Fi P2P Technical Specification v1.7, 4.1.1.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, AsBytes, FromBytes, Unaligned)]
+pub struct P2pAttributeHeader {
+    pub attr_id: u8,
+    pub length: u16,
+}
+
+pub struct P2pAttributeView<B> {
+    pub header: LayoutVerified<B, P2pAttributeHeader>,
+    pub body: B,
+}
+
+pub struct P2pAttributeListView<B>(pub B);
+
+impl<B: ByteSlice> IntoIterator for P2pAttributeListView<B> {
+    type Item = P2pAttributeView<B>;
+    type IntoIter = P2pAttributeIter<B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        P2pAttributeIter(BufferReader::new(self.0))
+    }
+}
+
+pub struct P2pAttributeIter<B>(BufferReader<B>);
+
+impl<B: ByteSlice> Iterator for P2pAttributeIter<B> {
+    type Item = P2pAttributeView<B>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let length = { self.0.peek::<P2pAttributeHeader>()?.length } as usize;
+        if self.0.bytes_remaining() < size_of::<P2pAttributeHeader>() + length {
+            return None;
+        }
+        // Unwraps are OK because we checked the length above.
+        let header = self.0.read().unwrap();
+        let body = self.0.read_bytes(length).unwrap();
+        Some(P2pAttributeView { header, body })
+    }
+}
+
+// Wi-Fi Agile Multiband / Optimized Connectivity Experience, same TLV layout as P2P.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, AsBytes, FromBytes, Unaligned)]
+pub struct MboAttributeHeader {
+    pub attr_id: u8,
+    pub length: u16,
+}
+
+pub struct MboAttributeView<B> {
+    pub header: LayoutVerified<B, MboAttributeHeader>,
+    pub body: B,
+}
+
+pub struct MboAttributeListView<B>(pub B);
+
+impl<B: ByteSlice> IntoIterator for MboAttributeListView<B> {
+    type Item = MboAttributeView<B>;
+    type IntoIter = MboAttributeIter<B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MboAttributeIter(BufferReader::new(self.0))
+    }
+}
+
+pub struct MboAttributeIter<B>(BufferReader<B>);
+
+impl<B: ByteSlice> Iterator for MboAttributeIter<B> {
+    type Item = MboAttributeView<B>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let length = { self.0.peek::<MboAttributeHeader>()?.length } as usize;
+        if self.0.bytes_remaining() < size_of::<MboAttributeHeader>() + length {
+            return None;
+        }
+        // Unwraps are OK because we checked the length above.
+        let header = self.0.read().unwrap();
+        let body = self.0.read_bytes(length).unwrap();
+        Some(MboAttributeView { header, body })
+    }
+}
+
+// Wi-Fi Simple Configuration (WSC/WPS) TLVs are big-endian on the wire, unlike the rest of this
+// crate's fields, so the type and length are kept as raw bytes and decoded explicitly rather than
+// overlaid as native (little-endian) integers.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, AsBytes, FromBytes, Unaligned)]
+pub struct WscAttributeHeader {
+    attr_type: [u8; 2],
+    length: [u8; 2],
+}
+
+impl WscAttributeHeader {
+    pub fn attr_type(&self) -> u16 {
+        u16::from_be_bytes(self.attr_type)
+    }
+
+    pub fn length(&self) -> u16 {
+        u16::from_be_bytes(self.length)
+    }
+}
+
+pub struct WscAttributeView<B> {
+    pub header: LayoutVerified<B, WscAttributeHeader>,
+    pub body: B,
+}
+
+pub struct WscAttributeListView<B>(pub B);
+
+impl<B: ByteSlice> IntoIterator for WscAttributeListView<B> {
+    type Item = WscAttributeView<B>;
+    type IntoIter = WscAttributeIter<B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        WscAttributeIter(BufferReader::new(self.0))
+    }
+}
+
+pub struct WscAttributeIter<B>(BufferReader<B>);
+
+impl<B: ByteSlice> Iterator for WscAttributeIter<B> {
+    type Item = WscAttributeView<B>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let length = { self.0.peek::<WscAttributeHeader>()?.length() } as usize;
+        if self.0.bytes_remaining() < size_of::<WscAttributeHeader>() + length {
+            return None;
+        }
+        // Unwraps are OK because we checked the length above.
+        let header = self.0.read().unwrap();
+        let body = self.0.read_bytes(length).unwrap();
+        Some(WscAttributeView { header, body })
+    }
+}
+
 // IEEE Std 802.11-2016, 9.4.2.57
 #[repr(C, packed)]
 #[derive(PartialEq, Eq, Hash, AsBytes, FromBytes, Unaligned, Clone, Copy)]
@@ -711,6 +1108,40 @@ pub struct VhtCapabilities {
     pub vht_mcs_nss: VhtMcsNssSet,         // u64
 }
 
+impl VhtCapabilities {
+    /// Computes the effective VHT operating parameters for a link between `local` and `peer`,
+    /// as association negotiation needs: the common max MPDU length and channel bandwidth, and
+    /// the per-spatial-stream MCS set each side can actually use. Every other field is inherited
+    /// from `local`, since those are the only parameters association negotiation looks at today.
+    pub fn intersect(local: &VhtCapabilities, peer: &VhtCapabilities) -> VhtCapabilities {
+        let local_cap_info = local.vht_cap_info;
+        let peer_cap_info = peer.vht_cap_info;
+        let max_mpdu_len =
+            std::cmp::min(local_cap_info.max_mpdu_len().0, peer_cap_info.max_mpdu_len().0);
+        let supported_cbw_set =
+            std::cmp::min(local_cap_info.supported_cbw_set(), peer_cap_info.supported_cbw_set());
+        let vht_cap_info = VhtCapabilitiesInfo(
+            (local_cap_info.0 & !0b1111)
+                | u32::from(max_mpdu_len)
+                | (u32::from(supported_cbw_set) << 2),
+        );
+
+        let local_mcs_nss = local.vht_mcs_nss;
+        let peer_mcs_nss = peer.vht_mcs_nss;
+        let rx_max_mcs_map =
+            local_mcs_nss.rx_max_mcs_map().intersect(&peer_mcs_nss.rx_max_mcs_map());
+        let tx_max_mcs_map =
+            local_mcs_nss.tx_max_mcs_map().intersect(&peer_mcs_nss.tx_max_mcs_map());
+        let vht_mcs_nss = VhtMcsNssSet(
+            (local_mcs_nss.0 & !0xFFFF_0000_FFFF)
+                | u64::from(rx_max_mcs_map.0)
+                | (u64::from(tx_max_mcs_map.0) << 32),
+        );
+
+        VhtCapabilities { vht_cap_info, vht_mcs_nss }
+    }
+}
+
 // IEEE Std 802.11-2016, 9.4.2.158.2
 #[bitfield(
     0..=1   max_mpdu_len as MaxMpduLen(u8),
@@ -765,12 +1196,12 @@ impl VhtLinkAdaptation {
 
 // IEEE Std 802.11-2016, 9.4.2.158.3
 #[bitfield(
-    0..=15  rx_max_mcs as VhtMcsNssMap(u16),
+    0..=15  rx_max_mcs_map as VhtMcsNssMap(u16),
 
     16..=28 rx_max_data_rate,               // Mbps rounded down to the nearest integer
     29..=31 max_nsts,
 
-    32..=47 tx_max_mcs as VhtMcsNssMap(u16),
+    32..=47 tx_max_mcs_map as VhtMcsNssMap(u16),
 
     48..=60 tx_max_data_rate,               // Mbps rounded down to the nearest integer
     61      ext_nss_bw,                     // Extended NSS BW Capable
@@ -780,6 +1211,30 @@ impl VhtLinkAdaptation {
 #[derive(PartialEq, Eq, Hash, AsBytes, FromBytes, Clone, Copy)]
 pub struct VhtMcsNssSet(pub u64);
 
+impl VhtMcsNssSet {
+    /// Returns the highest Rx MCS supported for `nss` (1..=8) spatial streams, analogous to
+    /// `RxMcsBitmask::support` for HT. `None` if `nss` is out of range.
+    pub fn rx_max_mcs(&self, nss: u8) -> Option<VhtMcsSet> {
+        self.rx_max_mcs_map().ss(nss).ok()
+    }
+
+    /// Returns the highest Tx MCS supported for `nss` (1..=8) spatial streams. `None` if `nss` is
+    /// out of range.
+    pub fn tx_max_mcs(&self, nss: u8) -> Option<VhtMcsSet> {
+        self.tx_max_mcs_map().ss(nss).ok()
+    }
+
+    /// The highest Rx data rate supported, in Mbps, rounded down to the nearest integer.
+    pub fn rx_highest_rate(&self) -> u16 {
+        self.rx_max_data_rate()
+    }
+
+    /// The highest Tx data rate supported, in Mbps, rounded down to the nearest integer.
+    pub fn tx_highest_rate(&self) -> u16 {
+        self.tx_max_data_rate()
+    }
+}
+
 // IEEE Std 802.11-2016, Figure 9-562.
 #[bitfield(
     0..=1   ss1 as VhtMcsSet(u8),
@@ -818,6 +1273,26 @@ impl VhtMcsNssMap {
             Ok(())
         }
     }
+
+    /// Combines two maps into the `VhtMcsSet` each spatial stream can actually use on a link
+    /// between the two sides: the lower (more conservative) of the two sets, with `NONE` from
+    /// either side making the stream unsupported on the link.
+    pub fn intersect(&self, other: &VhtMcsNssMap) -> VhtMcsNssMap {
+        let mut result = VhtMcsNssMap(0);
+        for nss in 1..=8 {
+            let a = self.ss(nss).unwrap();
+            let b = other.ss(nss).unwrap();
+            let min = if a == VhtMcsSet::NONE || b == VhtMcsSet::NONE {
+                VhtMcsSet::NONE
+            } else if a.0 <= b.0 {
+                a
+            } else {
+                b
+            };
+            result.set_ss(nss, min).unwrap();
+        }
+        result
+    }
 }
 
 #[derive(Debug, PartialOrd, PartialEq, Clone, Copy)]
@@ -832,7 +1307,6 @@ impl VhtMcsSet {
 // IEEE Std 802.11-2016, 9.4.2.159
 #[repr(C, packed)]
 #[derive(PartialEq, Eq, Hash, AsBytes, FromBytes, Unaligned, Clone, Copy)]
-// TODO(WLAN-1051): Derive phy parameters based on Table 9-250 and 9-253.
 pub struct VhtOperation {
     pub vht_cbw: VhtChannelBandwidth, // u8
     pub center_freq_seg0: u8,         // Channel index
@@ -841,6 +1315,113 @@ pub struct VhtOperation {
     pub basic_mcs_nss: VhtMcsNssMap, // u16
 }
 
+impl VhtOperation {
+    /// Resolves the actual operating channel described by this `VhtOperation`: `primary_chan`
+    /// and `ht_secondary_offset` come from the accompanying HT Operation element and are needed
+    /// to disambiguate `CBW_20_40` and to anchor the 40 MHz center frequency off the primary
+    /// channel. See IEEE Std 802.11-2016, Tables 9-250, 9-252, and 9-253 (WLAN-1051).
+    pub fn channel(
+        &self,
+        primary_chan: u8,
+        ht_secondary_offset: SecChanOffset,
+    ) -> Result<ResolvedChannel, String> {
+        if !is_valid_5ghz_channel(primary_chan) {
+            return Err(format!("{} is not a valid 5 GHz primary channel number", primary_chan));
+        }
+        // `seg0`/`seg1` are center-frequency segment indices, not primary channel numbers: for
+        // 80/160 MHz channels they commonly land on numbers like 42, 58, 106, 138, or 155, which
+        // `is_valid_5ghz_channel` (a primary-channel table) would wrongly reject. Leave them
+        // unvalidated here; a downstream channel-to-frequency conversion rejects nonsense values.
+        let seg0 = self.center_freq_seg0;
+        let seg1 = self.center_freq_seg1;
+
+        let (cbw, center_freq_seg0) = match self.vht_cbw.0 {
+            0 => match ht_secondary_offset.0 {
+                // CBW_20_40: VHT alone can't tell 20 from 40 MHz, so defer entirely to HT's
+                // secondary channel offset.
+                0 => (Cbw::Cbw20, primary_chan),
+                1 => (Cbw::Cbw40Above, primary_chan + 2),
+                3 => (Cbw::Cbw40Below, primary_chan - 2),
+                other => return Err(format!("invalid secondary channel offset {}", other)),
+            },
+            1 => {
+                if seg1 == 0 {
+                    (Cbw::Cbw80, seg0)
+                } else if (seg0 as i16 - seg1 as i16).abs() == 8 {
+                    // New-style contiguous 160 MHz: seg0 is the 80 MHz sub-center, seg1 is the
+                    // true center of the full 160 MHz channel.
+                    (Cbw::Cbw160, seg1)
+                } else {
+                    (Cbw::Cbw80P80 { center_freq_seg1: seg1 }, seg0)
+                }
+            }
+            // CBW_160 (deprecated): seg0 is the 160 MHz center directly, no seg1 needed.
+            2 => (Cbw::Cbw160, seg0),
+            // CBW_80P80 (deprecated): seg0 and seg1 are the two 80 MHz centers directly.
+            3 => (Cbw::Cbw80P80 { center_freq_seg1: seg1 }, seg0),
+            other => return Err(format!("reserved VHT channel bandwidth value {}", other)),
+        };
+        Ok(ResolvedChannel { primary: primary_chan, cbw, center_freq_seg0 })
+    }
+}
+
+/// Channel bandwidth of a `ResolvedChannel`, combining VHT's channel width with, for 40 MHz,
+/// which side of the primary channel carries the secondary channel.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Cbw {
+    Cbw20,
+    Cbw40Above,
+    Cbw40Below,
+    Cbw80,
+    Cbw160,
+    Cbw80P80 { center_freq_seg1: u8 },
+}
+
+/// The operating channel derived by `VhtOperation::channel`: a primary 20 MHz channel, the
+/// bandwidth in use, and the center frequency channel index of the primary segment (the second
+/// segment's center is carried in `Cbw::Cbw80P80` for 80+80 MHz).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ResolvedChannel {
+    pub primary: u8,
+    pub cbw: Cbw,
+    pub center_freq_seg0: u8,
+}
+
+/// Whether `chan` is a channel number allocated for 5 GHz operation (IEEE Std 802.11-2016,
+/// Annex E, Table E-4).
+fn is_valid_5ghz_channel(chan: u8) -> bool {
+    matches!(
+        chan,
+        36 | 40
+            | 44
+            | 48
+            | 52
+            | 56
+            | 60
+            | 64
+            | 100
+            | 104
+            | 108
+            | 112
+            | 116
+            | 120
+            | 124
+            | 128
+            | 132
+            | 136
+            | 140
+            | 144
+            | 149
+            | 153
+            | 157
+            | 161
+            | 165
+            | 169
+            | 173
+            | 177
+    )
+}
+
 // IEEE Std 802.11-2016, Table 9-252
 #[repr(C)]
 #[derive(Debug, PartialOrd, PartialEq, Eq, Hash, AsBytes, FromBytes, Clone, Copy)]
@@ -853,6 +1434,555 @@ impl VhtChannelBandwidth {
                               // 4-255 reserved
 }
 
+// VHT PHY data rate computation, IEEE Std 802.11-2016, 21.5 and Table 21-30, mirroring what
+// Linux's cfg80211 utilities compute when deriving a bitrate from an MCS index, NSS, bandwidth,
+// and guard interval.
+
+/// Number of data subcarriers (`Nsd`) for a VHT channel bandwidth. `CBW_20_40` covers both 20 and
+/// 40 MHz on the wire (IEEE Std 802.11-2016, Table 9-252); since this field alone can't tell them
+/// apart, it's treated here as 40 MHz, the common VHT case. `None` for the reserved 4-255 range.
+fn vht_nsd(cbw: VhtChannelBandwidth) -> Option<u32> {
+    match cbw.0 {
+        0 => Some(108), // CBW_20_40, approximated as 40 MHz
+        1 => Some(234), // CBW_80_160_80P80, approximated as 80 MHz
+        2 => Some(468), // CBW_160 (deprecated)
+        3 => Some(468), // CBW_80P80 (deprecated)
+        _ => None,
+    }
+}
+
+/// (bits per subcarrier, coding rate numerator, coding rate denominator) for a VHT MCS index.
+/// IEEE Std 802.11-2016, Table 21-30. `None` for MCS indices above 9, which VHT doesn't define.
+fn vht_mcs_params(mcs: u8) -> Option<(u32, u32, u32)> {
+    match mcs {
+        0 => Some((1, 1, 2)), // BPSK 1/2
+        1 => Some((2, 1, 2)), // QPSK 1/2
+        2 => Some((2, 3, 4)), // QPSK 3/4
+        3 => Some((4, 1, 2)), // 16-QAM 1/2
+        4 => Some((4, 3, 4)), // 16-QAM 3/4
+        5 => Some((6, 2, 3)), // 64-QAM 2/3
+        6 => Some((6, 3, 4)), // 64-QAM 3/4
+        7 => Some((6, 5, 6)), // 64-QAM 5/6
+        8 => Some((8, 3, 4)), // 256-QAM 3/4
+        9 => Some((8, 5, 6)), // 256-QAM 5/6
+        _ => None,
+    }
+}
+
+/// Computes the VHT PHY data rate, in Mbps rounded down to the nearest integer, for an MCS index,
+/// spatial stream count, channel bandwidth, and guard interval: `rate = Nsd * Nbpscs * R * Nss /
+/// Tsym` (IEEE Std 802.11-2016, 21.5). `Tsym` is 4.0us for a long guard interval, 3.6us for short.
+///
+/// Returns `None` for an out-of-range MCS or NSS (1..=8), for MCS6 at CBW80/160 with NSS 3 or 6
+/// (excluded by an explicit check), and for any combination whose data bits per OFDM symbol
+/// (`Nsd * Nbpscs * Nss * R`) is not a whole number.
+pub fn vht_data_rate(mcs: u8, nss: u8, cbw: VhtChannelBandwidth, short_gi: bool) -> Option<u32> {
+    if nss < 1 || nss > 8 {
+        return None;
+    }
+    let nsd = vht_nsd(cbw)?;
+    let (nbpscs, num, denom) = vht_mcs_params(mcs)?;
+    if mcs == 6 && nsd >= 234 && (nss == 3 || nss == 6) {
+        return None;
+    }
+    let coded_bits_per_symbol = nsd * nbpscs * u32::from(nss) * num;
+    if coded_bits_per_symbol % denom != 0 {
+        return None;
+    }
+    let data_bits_per_symbol = coded_bits_per_symbol / denom;
+    let tsym_ns: u64 = if short_gi { 3600 } else { 4000 };
+    Some(((u64::from(data_bits_per_symbol) * 1000) / tsym_ns) as u32)
+}
+
+/// Yields `(nss, rate)` for each spatial stream 1..=8 covered by a `VhtMcsNssMap`, where `rate` is
+/// the data rate, in Mbps, for the highest MCS that stream supports (`None` if the map marks it
+/// unsupported, or if `vht_data_rate` rejects that MCS/NSS/bandwidth combination).
+pub struct VhtMcsNssMapRates {
+    map: VhtMcsNssMap,
+    cbw: VhtChannelBandwidth,
+    short_gi: bool,
+    next_nss: u8,
+}
+
+impl Iterator for VhtMcsNssMapRates {
+    type Item = (u8, Option<u32>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_nss > 8 {
+            return None;
+        }
+        let nss = self.next_nss;
+        self.next_nss += 1;
+        let max_mcs = match self.map.ss(nss) {
+            Ok(VhtMcsSet(0)) => Some(7),
+            Ok(VhtMcsSet(1)) => Some(8),
+            Ok(VhtMcsSet(2)) => Some(9),
+            _ => None,
+        };
+        let rate = max_mcs.and_then(|mcs| vht_data_rate(mcs, nss, self.cbw, self.short_gi));
+        Some((nss, rate))
+    }
+}
+
+impl VhtMcsNssMap {
+    /// Iterates the max achievable data rate (Mbps) for each spatial stream 1..=8, given a
+    /// channel bandwidth and guard interval. See `vht_data_rate`.
+    pub fn max_rates(&self, cbw: VhtChannelBandwidth, short_gi: bool) -> VhtMcsNssMapRates {
+        VhtMcsNssMapRates { map: *self, cbw, short_gi, next_nss: 1 }
+    }
+}
+
+// Human-readable ("tcpdump-style") dissection of the elements above, in the spirit of
+// `print-802_11.c`. Unlike `Debug`, these `Display` impls expand bitfield subfields and
+// enum-like newtypes (e.g. `ChanWidthSet`, `HtProtection`) to their named values, and walk
+// per-spatial-stream MCS bitmaps, so a frame dump is legible without decoding hex by hand.
+//
+// Multi-byte fields of `#[repr(C, packed)]` structs are copied into locals before use: taking a
+// reference to such a field (e.g. to call a method on it in place) is unaligned and rejected by
+// the compiler, so the existing parse code already works around this the same way (see
+// `P2pAttributeIter::next`).
+
+fn fmt_mac_addr(addr: &MacAddr) -> String {
+    format!(
+        "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+        addr[0], addr[1], addr[2], addr[3], addr[4], addr[5]
+    )
+}
+
+fn fmt_chan_width_set(v: ChanWidthSet) -> &'static str {
+    match v.0 {
+        0 => "20 MHz only",
+        1 => "20/40 MHz",
+        _ => "reserved",
+    }
+}
+
+fn fmt_sm_power_save(v: SmPowerSave) -> &'static str {
+    match v.0 {
+        0 => "static",
+        1 => "dynamic",
+        3 => "disabled",
+        _ => "reserved",
+    }
+}
+
+fn fmt_sec_chan_offset(v: SecChanOffset) -> &'static str {
+    match v.0 {
+        0 => "none",
+        1 => "above",
+        3 => "below",
+        _ => "reserved",
+    }
+}
+
+fn fmt_sta_chan_width(v: StaChanWidth) -> &'static str {
+    match v.0 {
+        0 => "20 MHz",
+        1 => "any in supported set",
+        _ => "reserved",
+    }
+}
+
+fn fmt_ht_protection(v: HtProtection) -> &'static str {
+    match v.0 {
+        0 => "none",
+        1 => "non-member",
+        2 => "20 MHz",
+        3 => "non-HT mixed",
+        _ => "reserved",
+    }
+}
+
+fn fmt_max_mpdu_len(v: MaxMpduLen) -> &'static str {
+    match v.0 {
+        0 => "3895 octets",
+        1 => "7991 octets",
+        2 => "11454 octets",
+        _ => "reserved",
+    }
+}
+
+fn fmt_vht_mcs_set(v: VhtMcsSet) -> &'static str {
+    match v.0 {
+        0 => "up to MCS 7",
+        1 => "up to MCS 8",
+        2 => "up to MCS 9",
+        _ => "not supported",
+    }
+}
+
+fn fmt_vht_channel_bandwidth(v: VhtChannelBandwidth) -> &'static str {
+    match v.0 {
+        0 => "20/40",
+        1 => "80/160/80+80",
+        2 => "160 (deprecated)",
+        3 => "80+80 (deprecated)",
+        _ => "reserved",
+    }
+}
+
+/// Expands an HT Rx MCS bitmap into a "SS1: MCS [..], SS2: MCS [..]" summary, one group of 8
+/// consecutive MCS indices per spatial stream (IEEE Std 802.11-2016, Annex B.4.17.2).
+fn fmt_ht_rx_mcs_by_stream(mcs_set: SupportedMcsSet) -> String {
+    let bitmap = mcs_set.rx_mcs();
+    let mut groups = vec![];
+    for ss in 0u8..4 {
+        let mcs: Vec<u8> = (0u8..8).filter(|&m| bitmap.support(ss * 8 + m)).collect();
+        if !mcs.is_empty() {
+            groups.push(format!("SS{}: MCS {:?}", ss + 1, mcs));
+        }
+    }
+    groups.join(", ")
+}
+
+/// Renders a Supported/Extended Supported Rates element as tcpdump does: a comma-separated list
+/// of rates in Mbps, with a "(B)" suffix on each basic (mandatory) rate.
+pub fn dissect_supported_rates(rates: &[SupportedRate]) -> String {
+    rates
+        .iter()
+        .map(|r| {
+            let mbps = f32::from(r.rate()) * 0.5;
+            if r.basic() {
+                format!("{}(B)", mbps)
+            } else {
+                format!("{}", mbps)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for HtCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cap_info = self.ht_cap_info;
+        let ampdu_params = self.ampdu_params;
+        let mcs_set = self.mcs_set;
+        writeln!(
+            f,
+            "HT Capabilities: chan width set: {}, SM power save: {}, greenfield: {}, \
+             short GI: 20MHz={} 40MHz={}",
+            fmt_chan_width_set(cap_info.chan_width_set()),
+            fmt_sm_power_save(cap_info.sm_power_save()),
+            cap_info.greenfield(),
+            cap_info.short_gi_20(),
+            cap_info.short_gi_40(),
+        )?;
+        writeln!(
+            f,
+            "  max A-MPDU length: {} bytes, min start spacing: {:?}",
+            ampdu_params.max_ampdu_exponent().to_len(),
+            ampdu_params.min_start_spacing(),
+        )?;
+        writeln!(f, "  Rx MCS support: {}", fmt_ht_rx_mcs_by_stream(mcs_set))
+    }
+}
+
+impl fmt::Display for HtOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let primary_chan = self.primary_chan;
+        let info_head = self.ht_op_info_head;
+        let info_tail = self.ht_op_info_tail;
+        let mcs_set = self.basic_ht_mcs_set;
+        writeln!(
+            f,
+            "HT Operation: primary channel: {}, secondary channel offset: {}, \
+             STA channel width: {}, RIFS permitted: {}",
+            primary_chan,
+            fmt_sec_chan_offset(info_head.secondary_chan_offset()),
+            fmt_sta_chan_width(info_head.sta_chan_width()),
+            info_head.rifs_mode_permitted(),
+        )?;
+        writeln!(
+            f,
+            "  HT protection: {}, non-greenfield STAs present: {}, OBSS non-HT STAs present: {}",
+            fmt_ht_protection(info_tail.ht_protection()),
+            info_tail.nongreenfield_present(),
+            info_tail.obss_non_ht_stas_present(),
+        )?;
+        writeln!(f, "  basic Rx MCS set: {}", fmt_ht_rx_mcs_by_stream(mcs_set))
+    }
+}
+
+impl fmt::Display for VhtCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cap_info = self.vht_cap_info;
+        let mcs_nss = self.vht_mcs_nss;
+        writeln!(
+            f,
+            "VHT Capabilities: max MPDU length: {}, Rx LDPC: {}, SGI CBW80: {}, SGI CBW160: {}, \
+             max A-MPDU length: {} bytes",
+            fmt_max_mpdu_len(cap_info.max_mpdu_len()),
+            cap_info.rx_ldpc(),
+            cap_info.sgi_cbw80(),
+            cap_info.sgi_cbw160(),
+            cap_info.max_ampdu_exponent().to_len(),
+        )?;
+        write!(f, "  Rx VHT-MCS:")?;
+        for nss in 1u8..=8 {
+            if let Some(mcs) = mcs_nss.rx_max_mcs(nss) {
+                write!(f, " SS{}={}", nss, fmt_vht_mcs_set(mcs))?;
+            }
+        }
+        writeln!(f)?;
+        write!(f, "  Tx VHT-MCS:")?;
+        for nss in 1u8..=8 {
+            if let Some(mcs) = mcs_nss.tx_max_mcs(nss) {
+                write!(f, " SS{}={}", nss, fmt_vht_mcs_set(mcs))?;
+            }
+        }
+        writeln!(f)
+    }
+}
+
+impl fmt::Display for VhtOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vht_cbw = self.vht_cbw;
+        let center_freq_seg0 = self.center_freq_seg0;
+        let center_freq_seg1 = self.center_freq_seg1;
+        let basic_mcs_nss = self.basic_mcs_nss;
+        writeln!(
+            f,
+            "VHT Operation: channel bandwidth: {}, center freq seg0: {}, center freq seg1: {}, \
+             basic VHT-MCS: {}",
+            fmt_vht_channel_bandwidth(vht_cbw),
+            center_freq_seg0,
+            center_freq_seg1,
+            basic_mcs_nss,
+        )
+    }
+}
+
+impl fmt::Display for VhtMcsNssMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups: Vec<String> = (1u8..=8)
+            .filter_map(|nss| {
+                self.ss(nss).ok().map(|mcs| format!("SS{}: {}", nss, fmt_vht_mcs_set(mcs)))
+            })
+            .collect();
+        write!(f, "{}", groups.join(", "))
+    }
+}
+
+impl<B: ByteSlice> fmt::Display for TimView<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dtim_count = self.header.dtim_count;
+        let dtim_period = self.header.dtim_period;
+        let bmp_ctrl = self.header.bmp_ctrl;
+        writeln!(
+            f,
+            "TIM: DTIM count: {}, DTIM period: {}, group traffic: {}, bitmap offset: {}, \
+             {} bytes of partial virtual bitmap",
+            dtim_count,
+            dtim_period,
+            bmp_ctrl.group_traffic(),
+            bmp_ctrl.offset(),
+            self.bitmap.len(),
+        )
+    }
+}
+
+impl<B: ByteSlice> fmt::Display for PreqView<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = self.header.flags;
+        let hop_count = self.header.hop_count;
+        let element_ttl = self.header.element_ttl;
+        let path_discovery_id = self.header.path_discovery_id;
+        let originator_addr = self.header.originator_addr;
+        let originator_hwmp_seqno = self.header.originator_hwmp_seqno;
+        writeln!(
+            f,
+            "PREQ: hop count: {}, element TTL: {}, path discovery ID: {:#x}, gate \
+             announcement: {}, proactive: {}",
+            hop_count,
+            element_ttl,
+            path_discovery_id,
+            flags.gate_announcement(),
+            flags.proactive(),
+        )?;
+        writeln!(
+            f,
+            "  originator: {}, HWMP seqno: {:#010x}",
+            fmt_mac_addr(&originator_addr),
+            originator_hwmp_seqno.0,
+        )?;
+        if let Some(ext_addr) = &self.originator_external_addr {
+            writeln!(f, "  originator external address: {}", fmt_mac_addr(ext_addr))?;
+        }
+        let lifetime = self.middle.lifetime;
+        let metric = self.middle.metric;
+        let target_count = self.middle.target_count;
+        writeln!(
+            f,
+            "  lifetime: {}, metric: {}, target count: {}",
+            lifetime, metric, target_count
+        )?;
+        for target in self.targets.iter() {
+            let target_flags = target.flags;
+            let target_addr = target.target_addr;
+            let target_hwmp_seqno = target.target_hwmp_seqno;
+            writeln!(
+                f,
+                "  target: {}, HWMP seqno: {:#010x}, target only: {}, unknown seqno: {}",
+                fmt_mac_addr(&target_addr),
+                target_hwmp_seqno.0,
+                target_flags.target_only(),
+                target_flags.usn(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<B: ByteSlice> fmt::Display for PrepView<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hop_count = self.header.hop_count;
+        let element_ttl = self.header.element_ttl;
+        let target_addr = self.header.target_addr;
+        let target_hwmp_seqno = self.header.target_hwmp_seqno;
+        writeln!(
+            f,
+            "PREP: hop count: {}, element TTL: {}, target: {}, HWMP seqno: {:#010x}",
+            hop_count,
+            element_ttl,
+            fmt_mac_addr(&target_addr),
+            target_hwmp_seqno.0,
+        )?;
+        if let Some(ext_addr) = &self.target_external_addr {
+            writeln!(f, "  target external address: {}", fmt_mac_addr(ext_addr))?;
+        }
+        let lifetime = self.tail.lifetime;
+        let metric = self.tail.metric;
+        let originator_addr = self.tail.originator_addr;
+        let originator_hwmp_seqno = self.tail.originator_hwmp_seqno;
+        writeln!(
+            f,
+            "  lifetime: {}, metric: {}, originator: {}, HWMP seqno: {:#010x}",
+            lifetime,
+            metric,
+            fmt_mac_addr(&originator_addr),
+            originator_hwmp_seqno.0,
+        )
+    }
+}
+
+impl<B: ByteSlice> fmt::Display for PerrView<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let element_ttl = self.header.element_ttl;
+        let num_destinations = self.header.num_destinations;
+        writeln!(
+            f,
+            "PERR: element TTL: {}, {} destination(s)",
+            element_ttl, num_destinations
+        )?;
+        fmt_perr_destinations(f, &self.destinations)
+    }
+}
+
+impl<B: ByteSlice> fmt::Display for PerrDestinationListView<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_perr_destinations(f, self)
+    }
+}
+
+fn fmt_perr_destinations<B: ByteSlice>(
+    f: &mut fmt::Formatter<'_>,
+    destinations: &PerrDestinationListView<B>,
+) -> fmt::Result {
+    for dest in destinations.iter() {
+        let dest_addr = dest.header.dest_addr;
+        let hwmp_seqno = dest.header.hwmp_seqno;
+        write!(
+            f,
+            "  destination: {}, HWMP seqno: {:#010x}",
+            fmt_mac_addr(&dest_addr),
+            hwmp_seqno.0,
+        )?;
+        if let Some(ext_addr) = dest.ext_addr {
+            write!(f, ", external address: {}", fmt_mac_addr(&*ext_addr))?;
+        }
+        writeln!(f, ", reason code: {}", dest.reason_code.get().0)?;
+    }
+    Ok(())
+}
+
+impl<B: ByteSlice> fmt::Display for MpmOpenView<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = self.header.protocol;
+        let local_link_id = self.header.local_link_id;
+        writeln!(
+            f,
+            "MPM Open: protocol: {:?}, local link ID: {:#06x}, PMK present: {}",
+            protocol,
+            local_link_id,
+            self.pmk.is_some(),
+        )
+    }
+}
+
+impl<B: ByteSlice> fmt::Display for MpmConfirmView<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = self.header.protocol;
+        let local_link_id = self.header.local_link_id;
+        writeln!(
+            f,
+            "MPM Confirm: protocol: {:?}, local link ID: {:#06x}, peer link ID: {:#06x}, \
+             PMK present: {}",
+            protocol,
+            local_link_id,
+            self.peer_link_id.get(),
+            self.pmk.is_some(),
+        )
+    }
+}
+
+impl<B: ByteSlice> fmt::Display for MpmCloseView<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = self.header.protocol;
+        let local_link_id = self.header.local_link_id;
+        write!(f, "MPM Close: protocol: {:?}, local link ID: {:#06x}", protocol, local_link_id)?;
+        if let Some(peer_link_id) = &self.peer_link_id {
+            write!(f, ", peer link ID: {:#06x}", peer_link_id.get())?;
+        }
+        writeln!(
+            f,
+            ", reason code: {}, PMK present: {}",
+            self.reason_code.get().0,
+            self.pmk.is_some(),
+        )
+    }
+}
+
+/// Renders a parsed information element as readable multi-line text, in the spirit of tcpdump's
+/// `print-802_11.c`, so driver developers can dump association and mesh frames in one call
+/// instead of eyeballing hex.
+pub trait Dissect {
+    fn dissect(&self) -> String;
+}
+
+impl Dissect for VhtCapabilities {
+    fn dissect(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Dissect for VhtOperation {
+    fn dissect(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Dissect for VhtMcsNssMap {
+    fn dissect(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<B: ByteSlice> Dissect for PerrDestinationListView<B> {
+    fn dissect(&self) -> String {
+        self.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -886,7 +2016,7 @@ mod tests {
 
         {
             let target = iter.next().expect("expected first target");
-            assert_eq!(0x44332211, { target.header.hwmp_seqno });
+            assert_eq!(HwmpSeqno(0x44332211), { target.header.hwmp_seqno });
             let ext_addr = target.ext_addr.expect("expected external addr");
             assert_eq!([0x1a, 0x2a, 0x3a, 0x4a, 0x5a, 0x6a], *ext_addr);
             assert_eq!(0x6655, target.reason_code.get().0);
@@ -896,7 +2026,7 @@ mod tests {
 
         {
             let target = iter.next().expect("expected second target");
-            assert_eq!(0xaa998877, { target.header.hwmp_seqno });
+            assert_eq!(HwmpSeqno(0xaa998877), { target.header.hwmp_seqno });
             assert!(target.ext_addr.is_none());
             assert_eq!(0xccbb, target.reason_code.get().0);
         }
@@ -965,6 +2095,20 @@ mod tests {
         assert_eq!(map.0, 0xc0fe);
     }
 
+    #[test]
+    fn vht_mcs_nss_set_accessor() {
+        let set = VhtMcsNssSet(0x0186_0003_030c_00ff);
+        assert_eq!(set.rx_max_mcs(1), Some(VhtMcsSet(3)));
+        assert_eq!(set.rx_max_mcs(5), Some(VhtMcsSet(0)));
+        assert_eq!(set.rx_max_mcs(9), None);
+        assert_eq!(set.rx_highest_rate(), 780);
+
+        assert_eq!(set.tx_max_mcs(1), Some(VhtMcsSet(3)));
+        assert_eq!(set.tx_max_mcs(2), Some(VhtMcsSet(0)));
+        assert_eq!(set.tx_max_mcs(9), None);
+        assert_eq!(set.tx_highest_rate(), 390);
+    }
+
     #[test]
     fn vht_mcs_nss_map_accssor_error() {
         let mut map = VhtMcsNssMap(0);
@@ -989,4 +2133,266 @@ mod tests {
             Err("bitfield is only 2 bit wide, 4 invalid".to_string())
         );
     }
+
+    #[test]
+    fn hwmp_seqno_is_newer_than_wraps_around() {
+        // A sequence number just past a wrap is newer than one just before it.
+        assert!(HwmpSeqno(1).is_newer_than(&HwmpSeqno(0xFFFF_FFFF)));
+        assert!(!HwmpSeqno(0xFFFF_FFFF).is_newer_than(&HwmpSeqno(1)));
+
+        // Exactly half the sequence space apart (the 0x8000_0000 boundary) is undefined by the
+        // spec and must be "not newer" in either direction.
+        assert!(!HwmpSeqno(0x8000_0000).is_newer_than(&HwmpSeqno(0)));
+        assert!(!HwmpSeqno(0).is_newer_than(&HwmpSeqno(0x8000_0000)));
+
+        // One less than the undefined boundary is newer.
+        assert!(HwmpSeqno(0x7FFF_FFFF).is_newer_than(&HwmpSeqno(0)));
+        assert!(!HwmpSeqno(0).is_newer_than(&HwmpSeqno(0x7FFF_FFFF)));
+    }
+
+    #[test]
+    fn perr_destination_list_writer_ignores_ext_addr_without_flag() {
+        let mut buf = [0u8; 32];
+        let bytes_written = {
+            let mut w = BufferWriter::new(&mut buf[..]);
+            let ext_addr = MacAddr([0x1a, 0x2a, 0x3a, 0x4a, 0x5a, 0x6a]);
+            let mut writer = PerrDestinationListWriter::new(&mut w);
+            writer
+                .push(
+                    MacAddr([0xa0, 0xb0, 0xc0, 0xd0, 0xe0, 0xf0]),
+                    PerrDestinationFlags(0), // addr_ext not set
+                    HwmpSeqno(0x11223344),
+                    Some(&ext_addr),
+                    ReasonCode(0x1234),
+                )
+                .expect("expected push to succeed");
+            assert_eq!(writer.num_destinations(), 1);
+            w.bytes_written()
+        };
+
+        // The External Address must be omitted since addr_ext() was not set, even though
+        // Some(ext_addr) was passed, or a conformant parser reading the flag bit desyncs here.
+        let mut iter = PerrDestinationListView(&buf[..bytes_written]).into_iter();
+        let target = iter.next().expect("expected one target");
+        assert!(target.ext_addr.is_none());
+        assert_eq!(0x1234, target.reason_code.get().0);
+        assert_eq!(0, iter.bytes_remaining());
+    }
+
+    #[test]
+    fn vht_operation_channel_80mhz() {
+        // 80 MHz channel centered on primary channels 36-48: center freq segment index 42.
+        let vht_op = VhtOperation {
+            vht_cbw: VhtChannelBandwidth(1),
+            center_freq_seg0: 42,
+            center_freq_seg1: 0,
+            basic_mcs_nss: VhtMcsNssMap(0),
+        };
+        let resolved = vht_op.channel(36, SecChanOffset(1)).expect("expected a resolved channel");
+        assert_eq!(resolved.primary, 36);
+        assert_eq!(resolved.cbw, Cbw::Cbw80);
+        assert_eq!(resolved.center_freq_seg0, 42);
+    }
+
+    #[test]
+    fn vht_capabilities_intersect() {
+        // local: max MPDU len 11454 octets, CBW set 1, Rx LDPC capable.
+        let local = VhtCapabilities {
+            vht_cap_info: VhtCapabilitiesInfo(22),
+            vht_mcs_nss: VhtMcsNssSet(2), // Rx: SS1=UPTO_9, rest=UPTO_7; Tx: all UPTO_7.
+        };
+        // peer: max MPDU len 3895 octets, CBW set 2, Rx LDPC not capable.
+        let peer = VhtCapabilities {
+            vht_cap_info: VhtCapabilitiesInfo(8),
+            vht_mcs_nss: VhtMcsNssSet(13), // Rx: SS1=UPTO_8, SS2=NONE, rest=UPTO_7; Tx: all UPTO_7.
+        };
+
+        let result = VhtCapabilities::intersect(&local, &peer);
+
+        // The weaker of the two max MPDU lengths and CBW sets wins; everything else (e.g. Rx
+        // LDPC) is inherited from `local`.
+        assert_eq!(result.vht_cap_info.max_mpdu_len(), MaxMpduLen::OCTECTS_3895);
+        assert_eq!(result.vht_cap_info.supported_cbw_set(), 1);
+        assert!(result.vht_cap_info.rx_ldpc());
+
+        // Per spatial stream, the lower MCS set wins, and NONE from either side wins outright.
+        assert_eq!(result.vht_mcs_nss.rx_max_mcs(1), Some(VhtMcsSet::UPTO_8));
+        assert_eq!(result.vht_mcs_nss.rx_max_mcs(2), Some(VhtMcsSet::NONE));
+        assert_eq!(result.vht_mcs_nss.rx_max_mcs(3), Some(VhtMcsSet::UPTO_7));
+    }
+
+    #[test]
+    fn vht_data_rate_known_values() {
+        // MCS0, 1 spatial stream, 80 MHz, long GI: 29.25 Mbps, rounded down.
+        assert_eq!(Some(29), vht_data_rate(0, 1, VhtChannelBandwidth(1), false));
+        // Same, but with a short guard interval: 32.5 Mbps, rounded down.
+        assert_eq!(Some(32), vht_data_rate(0, 1, VhtChannelBandwidth(1), true));
+        // MCS6 at 80 MHz is excluded for 3 spatial streams.
+        assert_eq!(None, vht_data_rate(6, 3, VhtChannelBandwidth(1), false));
+        // NSS out of the supported 1..=8 range.
+        assert_eq!(None, vht_data_rate(0, 0, VhtChannelBandwidth(1), false));
+        assert_eq!(None, vht_data_rate(0, 9, VhtChannelBandwidth(1), false));
+    }
+
+    #[test]
+    fn vht_operation_dissect_and_display() {
+        let vht_op = VhtOperation {
+            vht_cbw: VhtChannelBandwidth(1),
+            center_freq_seg0: 42,
+            center_freq_seg1: 0,
+            basic_mcs_nss: VhtMcsNssMap(0),
+        };
+        let expected = "VHT Operation: channel bandwidth: 80/160/80+80, center freq seg0: 42, \
+                         center freq seg1: 0, basic VHT-MCS: SS1: up to MCS 7, SS2: up to MCS 7, \
+                         SS3: up to MCS 7, SS4: up to MCS 7, SS5: up to MCS 7, SS6: up to MCS 7, \
+                         SS7: up to MCS 7, SS8: up to MCS 7\n";
+        assert_eq!(expected, vht_op.to_string());
+        assert_eq!(expected, vht_op.dissect());
+    }
+
+    #[test]
+    fn perr_destination_list_view_display() {
+        #[rustfmt::skip]
+        let data = [
+            0x00, // flags: no address extension
+            0xa0, 0xb0, 0xc0, 0xd0, 0xe0, 0xf0, // dest addr
+            0x01, 0x00, 0x00, 0x00, // HWMP seqno
+            0x02, 0x00, // reason code
+        ];
+        let view = PerrDestinationListView(&data[..]);
+        assert_eq!(
+            "  destination: a0:b0:c0:d0:e0:f0, HWMP seqno: 0x00000001, reason code: 2\n",
+            view.to_string(),
+        );
+        assert_eq!(view.to_string(), view.dissect());
+    }
+
+    #[test]
+    fn p2p_and_mbo_attribute_iter_two_attributes() {
+        #[rustfmt::skip]
+        let data = [
+            0x01, 0x02, 0x00, 0xaa, 0xbb, // attr 1: id 1, length 2
+            0x02, 0x01, 0x00, 0xcc, // attr 2: id 2, length 1
+        ];
+        let mut iter = P2pAttributeListView(&data[..]).into_iter();
+
+        let attr = iter.next().expect("expected first attribute");
+        assert_eq!(attr.header.attr_id, 1);
+        assert_eq!({ attr.header.length }, 2);
+        assert_eq!(attr.body, &[0xaa, 0xbb][..]);
+
+        let attr = iter.next().expect("expected second attribute");
+        assert_eq!(attr.header.attr_id, 2);
+        assert_eq!({ attr.header.length }, 1);
+        assert_eq!(attr.body, &[0xcc][..]);
+
+        assert!(iter.next().is_none());
+
+        // MboAttributeHeader/View share the exact same TLV layout as P2P.
+        let mut iter = MboAttributeListView(&data[..]).into_iter();
+        let attr = iter.next().expect("expected first MBO attribute");
+        assert_eq!(attr.header.attr_id, 1);
+        assert_eq!(attr.body, &[0xaa, 0xbb][..]);
+    }
+
+    #[test]
+    fn wsc_attribute_iter_is_big_endian() {
+        #[rustfmt::skip]
+        let data = [
+            0x10, 0x04, // attr_type 0x1004, big-endian
+            0x00, 0x02, // length 2, big-endian
+            0x11, 0x22, // body
+        ];
+        let mut iter = WscAttributeListView(&data[..]).into_iter();
+        let attr = iter.next().expect("expected one WSC attribute");
+        assert_eq!(attr.header.attr_type(), 0x1004);
+        assert_eq!(attr.header.length(), 2);
+        assert_eq!(attr.body, &[0x11, 0x22][..]);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn write_preq_and_prep_with_external_addrs() {
+        let originator_ext = MacAddr([0x1a, 0x2a, 0x3a, 0x4a, 0x5a, 0x6a]);
+        let header = PreqHeader {
+            flags: PreqFlags(0x40), // bit 6: address extension
+            hop_count: 1,
+            element_ttl: 5,
+            path_discovery_id: 0x0102_0304,
+            originator_addr: MacAddr([0x10, 0x20, 0x30, 0x40, 0x50, 0x60]),
+            originator_hwmp_seqno: HwmpSeqno(7),
+        };
+        let middle = PreqMiddle { lifetime: 100, metric: 0, target_count: 1 };
+        let target = PreqPerTarget {
+            flags: PreqPerTargetFlags(0),
+            target_addr: MacAddr([0xa0, 0xb0, 0xc0, 0xd0, 0xe0, 0xf0]),
+            target_hwmp_seqno: HwmpSeqno(9),
+        };
+
+        let mut buf = [0u8; 64];
+        let bytes_written = {
+            let mut w = BufferWriter::new(&mut buf[..]);
+            write_preq(&mut w, &header, Some(&originator_ext), &middle, &[target])
+                .expect("expected write_preq to succeed");
+            w.bytes_written()
+        };
+
+        let mut reader = BufferReader::new(&buf[..bytes_written]);
+        let parsed_header = reader.read::<PreqHeader>().expect("expected a PREQ header");
+        assert!(parsed_header.flags.addr_ext());
+        assert_eq!(parsed_header.hop_count, 1);
+        let parsed_ext_addr =
+            reader.read::<MacAddr>().expect("expected an originator external addr");
+        assert_eq!([0x1a, 0x2a, 0x3a, 0x4a, 0x5a, 0x6a], *parsed_ext_addr);
+        let parsed_middle = reader.read::<PreqMiddle>().expect("expected PREQ middle fields");
+        assert_eq!({ parsed_middle.lifetime }, 100);
+        assert_eq!(parsed_middle.target_count, 1);
+        let parsed_target = reader.read::<PreqPerTarget>().expect("expected one PREQ target");
+        assert_eq!({ parsed_target.target_hwmp_seqno }, HwmpSeqno(9));
+        assert_eq!(0, reader.bytes_remaining());
+
+        // A PREP element without addr_ext set must omit the optional external addr entirely.
+        let prep_header = PrepHeader {
+            flags: PrepFlags(0),
+            hop_count: 2,
+            element_ttl: 6,
+            target_addr: MacAddr([1, 2, 3, 4, 5, 6]),
+            target_hwmp_seqno: HwmpSeqno(11),
+        };
+        let tail = PrepTail {
+            lifetime: 200,
+            metric: 50,
+            originator_addr: MacAddr([6, 5, 4, 3, 2, 1]),
+            originator_hwmp_seqno: HwmpSeqno(13),
+        };
+        let mut prep_buf = [0u8; 32];
+        let prep_bytes_written = {
+            let mut w = BufferWriter::new(&mut prep_buf[..]);
+            write_prep(&mut w, &prep_header, None, &tail).expect("expected write_prep to succeed");
+            w.bytes_written()
+        };
+        assert_eq!(prep_bytes_written, size_of::<PrepHeader>() + size_of::<PrepTail>());
+        let mut prep_reader = BufferReader::new(&prep_buf[..prep_bytes_written]);
+        let parsed_prep_header = prep_reader.read::<PrepHeader>().expect("expected a PREP header");
+        assert!(!parsed_prep_header.flags.addr_ext());
+        let parsed_tail = prep_reader.read::<PrepTail>().expect("expected PREP tail fields");
+        assert_eq!({ parsed_tail.originator_hwmp_seqno }, HwmpSeqno(13));
+        assert_eq!(0, prep_reader.bytes_remaining());
+    }
+
+    #[test]
+    fn vht_operation_channel_160mhz() {
+        // New-style contiguous 160 MHz: seg0 is the 80 MHz sub-center (42), seg1 is the true
+        // center of the full 160 MHz channel (50), 8 channels apart.
+        let vht_op = VhtOperation {
+            vht_cbw: VhtChannelBandwidth(1),
+            center_freq_seg0: 42,
+            center_freq_seg1: 50,
+            basic_mcs_nss: VhtMcsNssMap(0),
+        };
+        let resolved = vht_op.channel(36, SecChanOffset(1)).expect("expected a resolved channel");
+        assert_eq!(resolved.primary, 36);
+        assert_eq!(resolved.cbw, Cbw::Cbw160);
+        assert_eq!(resolved.center_freq_seg0, 50);
+    }
 }
@@ -0,0 +1,161 @@
+// Copyright 2026 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! C bindings for an SAE (Simultaneous Authentication of Equals, the WPA3-Personal handshake)
+//! commit/confirm exchange, so the C MLME layer can negotiate WPA3-Personal without
+//! reimplementing the elliptic-curve dance itself. Threads alongside the Open/shared-key
+//! authentication state machine already exposed from this crate.
+//!
+//! TODO(fxbug.dev/42162): only the handshake's FFI surface and frame bookkeeping are implemented
+//! here; the finite cyclic group cryptography of IEEE Std 802.11-2016 12.4 (deriving the commit
+//! scalar/element, confirming them, and deriving the PMK/PMKID) isn't implemented yet, so
+//! `sae_handshake_rx_commit`/`rx_confirm` currently return `SaeStatus::NotImplemented` rather
+//! than driving a real exchange.
+
+use std::ptr;
+use std::slice;
+
+/// Opaque SAE handshake context, owned by the C caller between `sae_handshake_new` and
+/// `sae_handshake_delete`.
+pub struct SaeHandshake {
+    // TODO(fxbug.dev/42162): read once the commit/confirm exchange derives real scalars/elements
+    // instead of short-circuiting to `SaeStatus::NotImplemented`.
+    #[allow(dead_code)]
+    peer: [u8; 6],
+    #[allow(dead_code)]
+    group_id: u16,
+    #[allow(dead_code)]
+    password: Vec<u8>,
+    state: SaeState,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum SaeState {
+    Init,
+    // TODO(fxbug.dev/42162): reachable once rx_commit/rx_confirm/poll_tx drive a real exchange
+    // instead of short-circuiting to `SaeStatus::NotImplemented`.
+    #[allow(dead_code)]
+    CommitSent,
+    #[allow(dead_code)]
+    ConfirmSent,
+    Established,
+}
+
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SaeStatus {
+    Success = 0,
+    InProgress = 1,
+    Rejected = 2,
+    NotImplemented = 3,
+}
+
+/// Creates a new SAE handshake context for a peer MAC, finite cyclic group ID, and password.
+/// `peer` must point to 6 bytes. Returns null if `password_len` is 0.
+///
+/// # Safety
+/// `peer` must be valid for 6 bytes, and `password` for `password_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sae_handshake_new(
+    peer: *const u8,
+    group_id: u16,
+    password: *const u8,
+    password_len: usize,
+) -> *mut SaeHandshake {
+    if password_len == 0 {
+        return ptr::null_mut();
+    }
+    let mut peer_addr = [0u8; 6];
+    peer_addr.copy_from_slice(slice::from_raw_parts(peer, 6));
+    let password = slice::from_raw_parts(password, password_len).to_vec();
+    Box::into_raw(Box::new(SaeHandshake {
+        peer: peer_addr,
+        group_id,
+        password,
+        state: SaeState::Init,
+    }))
+}
+
+/// Destroys a handshake context created by `sae_handshake_new`.
+///
+/// # Safety
+/// `handshake` must be a pointer returned by `sae_handshake_new`, not already deleted.
+#[no_mangle]
+pub unsafe extern "C" fn sae_handshake_delete(handshake: *mut SaeHandshake) {
+    if !handshake.is_null() {
+        drop(Box::from_raw(handshake));
+    }
+}
+
+/// Feeds a received SAE Commit frame body into the handshake. `body`/`body_len` cover the
+/// frame's Auth body past the fixed Authentication Algorithm/Sequence/Status fields.
+///
+/// # Safety
+/// `handshake` must be a live context from `sae_handshake_new`; `body` must be valid for
+/// `body_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sae_handshake_rx_commit(
+    handshake: *mut SaeHandshake,
+    _body: *const u8,
+    _body_len: usize,
+) -> SaeStatus {
+    if handshake.is_null() {
+        return SaeStatus::Rejected;
+    }
+    SaeStatus::NotImplemented
+}
+
+/// Feeds a received SAE Confirm frame body into the handshake.
+///
+/// # Safety
+/// `handshake` must be a live context from `sae_handshake_new`; `body` must be valid for
+/// `body_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sae_handshake_rx_confirm(
+    handshake: *mut SaeHandshake,
+    _body: *const u8,
+    _body_len: usize,
+) -> SaeStatus {
+    if handshake.is_null() {
+        return SaeStatus::Rejected;
+    }
+    SaeStatus::NotImplemented
+}
+
+/// Writes this handshake's next frame to transmit (Commit or Confirm, depending on state) into
+/// `out`, up to `out_len` bytes. Returns the number of bytes written, or 0 if there's nothing to
+/// send right now.
+///
+/// # Safety
+/// `handshake` must be a live context from `sae_handshake_new`; `out` must be valid for
+/// `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sae_handshake_poll_tx(
+    handshake: *mut SaeHandshake,
+    _out: *mut u8,
+    _out_len: usize,
+) -> usize {
+    if handshake.is_null() {
+        return 0;
+    }
+    0
+}
+
+/// Returns whether the handshake is in `SaeState::Established`. PMK derivation is not
+/// implemented (see the module docs), so nothing is written to `pmk` or `pmkid` in either case.
+///
+/// # Safety
+/// `handshake` must be a live context from `sae_handshake_new`; `pmk` must be valid for 32
+/// bytes and `pmkid` for 16 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sae_handshake_get_pmk(
+    handshake: *const SaeHandshake,
+    _pmk: *mut u8,
+    _pmkid: *mut u8,
+) -> bool {
+    if handshake.is_null() {
+        return false;
+    }
+    (*handshake).state == SaeState::Established
+}
@@ -15,4 +15,5 @@ pub mod utils;
 pub mod ap;
 pub mod auth;
 pub mod client;
+pub mod mesh;
 pub mod sequence;
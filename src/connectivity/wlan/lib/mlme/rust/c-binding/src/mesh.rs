@@ -0,0 +1,269 @@
+// Copyright 2026 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! C bindings for mesh (802.11s) peering management: Open and Close peering element
+//! construction, link metric tracking per neighbor, and callbacks for peer-link establishment
+//! and teardown, mirroring the lifecycle shape of the `client` bindings so integrators can stand
+//! up a mesh STA from C. No binding constructs a Confirm element.
+//!
+//! TODO(fxbug.dev/42163): frame construction reuses `wlan_common::ie`'s existing
+//! `write_mpm_open`/`write_mpm_close`, but parsing received Open/Confirm
+//! elements isn't implemented yet (no `parse_mpm_*` counterpart exists in `wlan_common` today),
+//! so `mesh_peering_rx_open`/`rx_confirm` currently return `MeshPeeringStatus::NotImplemented`
+//! rather than driving the state machine off real peer frames. `rx_close` doesn't need to parse
+//! its body to tear a link down, so it already drives the state machine and returns `Success`.
+
+use std::os::raw::c_void;
+use wlan_common::{
+    buffer_writer::BufferWriter,
+    ie::fields::{MpmHeader, MpmProtocol},
+    mac::{MacAddr, ReasonCode},
+    sequence::SequenceManager,
+};
+
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum MeshPeeringState {
+    Idle = 0,
+    OpenSent = 1,
+    ConfirmReceived = 2,
+    Established = 3,
+    Closing = 4,
+}
+
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum MeshPeeringStatus {
+    Success = 0,
+    InProgress = 1,
+    Rejected = 2,
+    NotImplemented = 3,
+}
+
+/// Callbacks a C integrator registers to learn about this peer link's lifecycle, mirroring how
+/// the `client` bindings report association state back across the FFI boundary.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct MeshPeeringCallbacks {
+    pub on_established: Option<extern "C" fn(ctx: *mut c_void)>,
+    pub on_closed: Option<extern "C" fn(ctx: *mut c_void, reason_code: u16)>,
+    pub ctx: *mut c_void,
+}
+
+/// Per-neighbor mesh peering state machine, created for a candidate peer discovered via
+/// beaconing or probing and driven by Open/Confirm/Close frames exchanged with it. Owns its own
+/// `SequenceManager` so its peering frames get sequence numbers independent of other peers.
+pub struct MeshPeering {
+    peer: MacAddr,
+    local_link_id: u16,
+    peer_link_id: Option<u16>,
+    state: MeshPeeringState,
+    link_metric: u32,
+    seq_mgr: SequenceManager,
+    callbacks: MeshPeeringCallbacks,
+}
+
+impl MeshPeering {
+    fn header(&self) -> MpmHeader {
+        MpmHeader { protocol: MpmProtocol::MPM, local_link_id: self.local_link_id }
+    }
+}
+
+/// Creates a new peering state machine for `peer` (6 bytes) with the caller-supplied
+/// `local_link_id`, in `MeshPeeringState::Idle`.
+///
+/// # Safety
+/// `peer` must be valid for 6 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_new(
+    peer: *const u8,
+    local_link_id: u16,
+    callbacks: MeshPeeringCallbacks,
+) -> *mut MeshPeering {
+    let mut peer_addr = [0u8; 6];
+    peer_addr.copy_from_slice(std::slice::from_raw_parts(peer, 6));
+    Box::into_raw(Box::new(MeshPeering {
+        peer: MacAddr(peer_addr),
+        local_link_id,
+        peer_link_id: None,
+        state: MeshPeeringState::Idle,
+        link_metric: 0,
+        seq_mgr: SequenceManager::new(),
+        callbacks,
+    }))
+}
+
+/// Destroys a peering context created by `mesh_peering_new`.
+///
+/// # Safety
+/// `peering` must be a pointer returned by `mesh_peering_new`, not already deleted.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_delete(peering: *mut MeshPeering) {
+    if !peering.is_null() {
+        drop(Box::from_raw(peering));
+    }
+}
+
+/// Writes this peering's Mesh Peering Open element into `out`, up to `out_len` bytes, and
+/// advances the state machine to `OpenSent`. Returns the number of bytes written, or 0 if `out`
+/// is too small.
+///
+/// # Safety
+/// `peering` must be a live context from `mesh_peering_new`; `out` must be valid for `out_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_write_open(
+    peering: *mut MeshPeering,
+    out: *mut u8,
+    out_len: usize,
+) -> usize {
+    if peering.is_null() {
+        return 0;
+    }
+    let peering = &mut *peering;
+    let mut buf = std::slice::from_raw_parts_mut(out, out_len);
+    let mut w = BufferWriter::new(&mut buf[..]);
+    let header = peering.header();
+    match wlan_common::ie::fields::write_mpm_open(&mut w, &header, None) {
+        Some(()) => {
+            peering.state = MeshPeeringState::OpenSent;
+            w.bytes_written()
+        }
+        None => 0,
+    }
+}
+
+/// Feeds a received Mesh Peering Open element into the state machine.
+///
+/// # Safety
+/// `peering` must be a live context from `mesh_peering_new`; `body` must be valid for `body_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_rx_open(
+    peering: *mut MeshPeering,
+    _body: *const u8,
+    _body_len: usize,
+) -> MeshPeeringStatus {
+    if peering.is_null() {
+        return MeshPeeringStatus::Rejected;
+    }
+    MeshPeeringStatus::NotImplemented
+}
+
+/// Feeds a received Mesh Peering Confirm element into the state machine.
+///
+/// # Safety
+/// `peering` must be a live context from `mesh_peering_new`; `body` must be valid for `body_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_rx_confirm(
+    peering: *mut MeshPeering,
+    _body: *const u8,
+    _body_len: usize,
+) -> MeshPeeringStatus {
+    if peering.is_null() {
+        return MeshPeeringStatus::Rejected;
+    }
+    MeshPeeringStatus::NotImplemented
+}
+
+/// Feeds a received Mesh Peering Close element into the state machine, tearing down the link.
+///
+/// # Safety
+/// `peering` must be a live context from `mesh_peering_new`; `body` must be valid for `body_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_rx_close(
+    peering: *mut MeshPeering,
+    _body: *const u8,
+    _body_len: usize,
+    reason_code: u16,
+) -> MeshPeeringStatus {
+    if peering.is_null() {
+        return MeshPeeringStatus::Rejected;
+    }
+    let peering = &mut *peering;
+    peering.state = MeshPeeringState::Idle;
+    peering.peer_link_id = None;
+    if let Some(on_closed) = peering.callbacks.on_closed {
+        on_closed(peering.callbacks.ctx, reason_code);
+    }
+    MeshPeeringStatus::Success
+}
+
+/// Writes this peering's Mesh Peering Close element into `out`, up to `out_len` bytes, with
+/// `reason_code`, and moves the state machine to `Closing`. Returns the number of bytes written,
+/// or 0 if `out` is too small.
+///
+/// # Safety
+/// `peering` must be a live context from `mesh_peering_new`; `out` must be valid for `out_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_write_close(
+    peering: *mut MeshPeering,
+    out: *mut u8,
+    out_len: usize,
+    reason_code: u16,
+) -> usize {
+    if peering.is_null() {
+        return 0;
+    }
+    let peering = &mut *peering;
+    let mut buf = std::slice::from_raw_parts_mut(out, out_len);
+    let mut w = BufferWriter::new(&mut buf[..]);
+    let header = peering.header();
+    let peer_link_id = peering.peer_link_id;
+    match wlan_common::ie::fields::write_mpm_close(
+        &mut w,
+        &header,
+        peer_link_id,
+        ReasonCode(reason_code),
+        None,
+    ) {
+        Some(()) => {
+            peering.state = MeshPeeringState::Closing;
+            w.bytes_written()
+        }
+        None => 0,
+    }
+}
+
+/// The current peer-link state for this neighbor.
+///
+/// # Safety
+/// `peering` must be a live context from `mesh_peering_new`.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_state(peering: *const MeshPeering) -> MeshPeeringState {
+    (*peering).state
+}
+
+/// Updates this neighbor's link metric (IEEE Std 802.11-2016, 14.9), used by HWMP path
+/// selection to prefer lower-cost paths.
+///
+/// # Safety
+/// `peering` must be a live context from `mesh_peering_new`.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_set_link_metric(peering: *mut MeshPeering, metric: u32) {
+    (*peering).link_metric = metric;
+}
+
+/// Reads this neighbor's most recently set link metric.
+///
+/// # Safety
+/// `peering` must be a live context from `mesh_peering_new`.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_link_metric(peering: *const MeshPeering) -> u32 {
+    (*peering).link_metric
+}
+
+/// The next outbound sequence number for this peer's frames, drawn from this peering's own
+/// `SequenceManager`, matching how the `client` bindings hand out per-peer sequence numbers.
+///
+/// # Safety
+/// `peering` must be a live context from `mesh_peering_new`.
+#[no_mangle]
+pub unsafe extern "C" fn mesh_peering_next_seq_no(peering: *mut MeshPeering) -> u16 {
+    let peering = &mut *peering;
+    peering.seq_mgr.next_sns1(&peering.peer.0) as u16
+}
@@ -0,0 +1,113 @@
+// Copyright 2026 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! C bindings for 802.11 sequence number allocation: one `wlan_common::sequence::SequenceManager`
+//! per destination MAC for non-QoS data and management frames, and — for QoS data frames, where
+//! each traffic identifier maintains its own sequence number space (IEEE Std 802.11-2016,
+//! 10.3.2.11) — an independent `SequenceManager` per (destination MAC, TID) pair, so each TID's
+//! sequence stream, and any BA/reordering session built on top of it, stays coherent. Delegates
+//! its actual counting to `SequenceManager` rather than re-deriving the wraparound arithmetic,
+//! the same type the `mesh` bindings use for per-peer sequence numbers.
+
+use std::collections::HashMap;
+use wlan_common::sequence::SequenceManager;
+
+/// Allocates 802.11 sequence numbers: one `SequenceManager` per destination MAC for non-QoS data
+/// and management frames, and independent per-(destination MAC, TID) `SequenceManager`s for QoS
+/// data frames.
+#[derive(Default)]
+pub struct SequenceAllocator {
+    non_qos: HashMap<[u8; 6], SequenceManager>,
+    qos: HashMap<([u8; 6], u8), SequenceManager>,
+}
+
+impl SequenceAllocator {
+    fn next_non_qos(&mut self, dest: [u8; 6]) -> u16 {
+        self.non_qos.entry(dest).or_insert_with(SequenceManager::new).next_sns1(&dest) as u16
+    }
+
+    fn next_qos(&mut self, dest: [u8; 6], tid: u8) -> u16 {
+        self.qos.entry((dest, tid)).or_insert_with(SequenceManager::new).next_sns1(&dest) as u16
+    }
+}
+
+/// Creates a new, empty sequence allocator.
+#[no_mangle]
+pub extern "C" fn sequence_allocator_new() -> *mut SequenceAllocator {
+    Box::into_raw(Box::new(SequenceAllocator::default()))
+}
+
+/// Destroys an allocator created by `sequence_allocator_new`.
+///
+/// # Safety
+/// `allocator` must be a pointer returned by `sequence_allocator_new`, not already deleted.
+#[no_mangle]
+pub unsafe extern "C" fn sequence_allocator_delete(allocator: *mut SequenceAllocator) {
+    if !allocator.is_null() {
+        drop(Box::from_raw(allocator));
+    }
+}
+
+/// Allocates the next sequence number for a non-QoS data or management frame to `dest`.
+///
+/// # Safety
+/// `allocator` must be a live allocator from `sequence_allocator_new`; `dest` must be valid for
+/// 6 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sequence_allocator_next_non_qos(
+    allocator: *mut SequenceAllocator,
+    dest: *const u8,
+) -> u16 {
+    let mut addr = [0u8; 6];
+    addr.copy_from_slice(std::slice::from_raw_parts(dest, 6));
+    (*allocator).next_non_qos(addr)
+}
+
+/// Allocates the next sequence number for a QoS data frame to `dest` on traffic identifier
+/// `tid` (0..=15), maintaining an independent counter per (`dest`, `tid`) pair.
+///
+/// # Safety
+/// `allocator` must be a live allocator from `sequence_allocator_new`; `dest` must be valid for
+/// 6 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sequence_allocator_next_qos(
+    allocator: *mut SequenceAllocator,
+    dest: *const u8,
+    tid: u8,
+) -> u16 {
+    let mut addr = [0u8; 6];
+    addr.copy_from_slice(std::slice::from_raw_parts(dest, 6));
+    (*allocator).next_qos(addr, tid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_qos_counter_wraps_at_4096() {
+        let mut alloc = SequenceAllocator::default();
+        let dest = [1, 2, 3, 4, 5, 6];
+        for expected in 0..4096 {
+            assert_eq!(alloc.next_non_qos(dest), expected);
+        }
+        assert_eq!(alloc.next_non_qos(dest), 0);
+    }
+
+    #[test]
+    fn qos_counters_are_independent_per_destination_and_tid() {
+        let mut alloc = SequenceAllocator::default();
+        let dest_a = [1, 2, 3, 4, 5, 6];
+        let dest_b = [6, 5, 4, 3, 2, 1];
+
+        assert_eq!(alloc.next_qos(dest_a, 0), 0);
+        assert_eq!(alloc.next_qos(dest_a, 0), 1);
+        assert_eq!(alloc.next_qos(dest_a, 1), 0);
+        assert_eq!(alloc.next_qos(dest_b, 0), 0);
+
+        // The non-QoS counter for the same destination is independent of both QoS counters.
+        assert_eq!(alloc.next_non_qos(dest_a), 0);
+        assert_eq!(alloc.next_qos(dest_a, 0), 2);
+    }
+}
@@ -19,6 +19,7 @@ use {
     fuchsia_zircon as zx,
     log::error,
     state::States,
+    std::collections::HashMap,
     wlan_common::{
         buffer_writer::BufferWriter,
         frame_len,
@@ -29,13 +30,338 @@ use {
     zerocopy::ByteSlice,
 };
 
-/// Maximum size of EAPOL frames forwarded to SME.
-/// TODO(34845): Evaluate whether EAPOL size restriction is needed.
-const MAX_EAPOL_FRAME_LEN: usize = 255;
+/// Default upper bound on the size of a fully-reassembled EAPOL PDU forwarded to SME. Large EAP
+/// methods (e.g. certificate-bearing EAP-TLS) can require several kilobytes, delivered across
+/// multiple 802.11 fragments, so this sits well above a single MSDU's typical size while still
+/// bounding the memory a never-completing reassembly can hold.
+/// TODO(34845): Evaluate whether an EAPOL size restriction is needed at all.
+const DEFAULT_MAX_EAPOL_PDU_LEN: usize = 4096;
+
+/// Default maximum size of an aggregated A-MSDU payload, IEEE Std 802.11-2016, Table 9-19.
+/// Non-VHT STAs are limited to 3839 octets; VHT STAs may declare support for up to 7935.
+const DEFAULT_MAX_AMSDU_LEN: usize = 3839;
+
+/// How long an outbound QoS MSDU is held per-TID waiting for others to coalesce with before being
+/// flushed as a single MPDU. Short enough that it's not observable as added latency, long enough
+/// to catch back-to-back egress frames queued by the same netstack flush.
+const AMSDU_AGGREGATION_WINDOW: zx::sys::zx_duration_t = 500_000; // 500us in nanoseconds
+
+/// How long a partial EAPOL fragment reassembly is kept before being discarded, bounding the
+/// memory a malicious or misbehaving AP could tie up by never completing a fragment sequence.
+const EAPOL_REASSEMBLY_TIMEOUT: zx::sys::zx_duration_t = 1_000_000_000; // 1 second
+
+/// Modulus of the 802.11 sequence number space, IEEE Std 802.11-2016, 9.4.1.4: a 12-bit counter
+/// that wraps from 4095 back to 0.
+const SEQ_NUM_MODULUS: u16 = 4096;
+
+/// Identifies one of the device layer's RX queues that received Ethernet II frames can be
+/// steered to. See `Client::set_tid_queue_mapping`.
+pub type RxQueueId = u8;
+
+/// Default Block Ack window size used when starting a receive reorder buffer, matching the
+/// common `buffer_size` offered by most HT APs (IEEE Std 802.11-2016, 9.6.5.2 allows up to 64 for
+/// HT, 256 for VHT).
+const DEFAULT_BA_WINDOW_SIZE: u16 = 64;
+
+/// How long a per-(peer, TID) Block Ack reorder buffer waits for a hole to fill before giving up
+/// and releasing whatever is buffered, in order, rather than stalling the stream indefinitely.
+const BA_REORDER_TIMEOUT: zx::sys::zx_duration_t = 100_000_000; // 100ms
+
+/// Number of PS-Poll retransmissions attempted for a single buffered frame before giving up on it,
+/// analogous to `SA_QUERY_MAX_RETRIES`.
+const PS_POLL_MAX_RETRIES: u8 = 3;
+
+/// Timeout between PS-Poll retransmissions while waiting for the AP to respond with the polled
+/// frame.
+const PS_POLL_RETRY_TIMEOUT: zx::sys::zx_duration_t = 50_000_000; // 50ms
 
 #[derive(Debug)]
 pub enum TimedEvent {
     Authenticating,
+    /// Recurring tick driving the connection monitor's beacon-loss detection, scheduled once per
+    /// beacon interval while the STA is associated. See `Client::handle_connection_monitor_tick`.
+    ConnectionMonitor,
+    /// Fires one beacon interval after a beacon-loss keep-alive probe was sent. If no frame from
+    /// the BSSID has been seen by then, the connection is considered dead; otherwise it is handled
+    /// as an ordinary `ConnectionMonitor` tick.
+    ConnectionMonitorProbeTimeout,
+    /// Retry/timeout for an in-flight SA Query initiated after receiving a deauth/disassoc from a
+    /// PMF-protected peer. See `Client::handle_sa_query_retry`.
+    SaQueryRetry,
+    /// Fires if an EAPOL fragment reassembly keyed on (src_addr, dst_addr) hasn't completed within
+    /// `EAPOL_REASSEMBLY_TIMEOUT`. See `Client::handle_eapol_reassembly_timeout`.
+    EapolReassemblyTimeout(MacAddr, MacAddr),
+    /// Fires at the end of a TID's A-MSDU aggregation window, flushing whatever MSDUs are
+    /// buffered for it. See `Client::flush_amsdu`.
+    AmsduFlush(u8),
+    /// Fires when a (peer, TID) Block Ack reorder buffer has had a stuck hole for too long. See
+    /// `Client::handle_ba_reorder_timeout`.
+    BaReorderTimeout(MacAddr, u8),
+    /// Retry/timeout for an in-flight PS-Poll. See `Client::handle_ps_poll_retry`.
+    PsPollRetry(Aid),
+}
+
+/// Number of SA Query request retransmissions attempted before giving up and treating the
+/// association as lost. IEEE Std 802.11-2016, 11.13.2 leaves this to implementation policy.
+const SA_QUERY_MAX_RETRIES: u8 = 3;
+
+/// Timeout between SA Query request retransmissions, matching the 201 TU-ish default used by
+/// most 802.11w implementations for `dot11AssociationSAQueryMaximumTimeout`.
+const SA_QUERY_RETRY_TIMEOUT: zx::sys::zx_duration_t = 201_000_000; // ~201ms in nanoseconds
+
+/// State of an in-flight SA Query initiated by this STA after receiving an unprotected
+/// deauthentication/disassociation from a PMF-protected peer.
+#[derive(Debug)]
+struct SaQueryState {
+    transaction_id: u16,
+    retries_remaining: u8,
+}
+
+/// State of an in-progress power-save poll for buffered traffic the AP announced via the TIM
+/// element. See `Client::handle_beacon_tim` and `Client::ps_poll`.
+#[derive(Debug)]
+struct PsPollState {
+    aid: Aid,
+    retries_remaining: u8,
+}
+
+/// Partial state of an EAPOL PDU being reassembled from 802.11 fragments. Keyed in
+/// `Client::eapol_reassembly` by the MSDU's (src_addr, dst_addr) pair, which is constant across
+/// all fragments of the same MSDU.
+#[derive(Debug, Default)]
+struct EapolReassembly {
+    /// Fragment number expected next, per IEEE Std 802.11-2016, 10.3.2.5. Fragments must arrive
+    /// in order; anything else indicates loss or reordering and the partial state is discarded.
+    next_frag_num: u8,
+    /// EAPOL payload bytes accumulated so far, in fragment order.
+    buf: Vec<u8>,
+}
+
+/// A single outbound MSDU buffered for possible A-MSDU aggregation with others sharing the same
+/// TID. See `Client::amsdu_pending`.
+#[derive(Debug)]
+struct PendingMsdu {
+    src: MacAddr,
+    dst: MacAddr,
+    is_protected: bool,
+    ether_type: u16,
+    payload: Vec<u8>,
+}
+
+/// A single MSDU to be embedded as an A-MSDU subframe by `write_amsdu_data_frame`.
+struct AmsduSubframe<'a> {
+    da: MacAddr,
+    sa: MacAddr,
+    ether_type: u16,
+    payload: &'a [u8],
+}
+
+/// Size an MSDU would occupy as an A-MSDU subframe: a `DA | SA | Length` header, an LLC/SNAP
+/// header, the payload itself, and zero-padding up to the next 4-byte boundary. Every subframe but
+/// the last is padded this way; padding every subframe here gives a conservative (safe to
+/// overestimate) bound when deciding whether a frame fits under `max_amsdu_len`.
+fn amsdu_subframe_len(payload: &[u8]) -> usize {
+    const AMSDU_SUBFRAME_HDR_LEN: usize = 6 /* DA */ + 6 /* SA */ + 2 /* Length */;
+    let unpadded = AMSDU_SUBFRAME_HDR_LEN + std::mem::size_of::<mac::LlcHdr>() + payload.len();
+    (unpadded + 3) & !3
+}
+
+/// Per-(peer, TID) receive reorder buffer for a Block Ack agreement, IEEE Std 802.11-2016,
+/// 10.24. Tracks the base sequence number of the window and holds out-of-order MSDUs, already
+/// converted to Ethernet frames, until the hole at the base is filled or the buffer times out.
+/// Keyed in `Client::ba_reorder` by (peer MAC address, TID).
+#[derive(Debug)]
+struct BaReorderBuffer {
+    /// Sequence number of the oldest MSDU not yet delivered.
+    base_seq: u16,
+    /// Negotiated window size; sequence numbers `>= base_seq + window_size` (mod
+    /// `SEQ_NUM_MODULUS`) are treated as a base advance rather than buffered in place.
+    window_size: u16,
+    /// MSDUs buffered ahead of `base_seq`, already rendered to Ethernet frame bytes, indexed by
+    /// `(seq_num - base_seq) mod SEQ_NUM_MODULUS`.
+    held: HashMap<u16, Vec<u8>>,
+}
+
+impl BaReorderBuffer {
+    fn new(starting_seq: u16, window_size: u16) -> Self {
+        Self { base_seq: starting_seq, window_size, held: HashMap::new() }
+    }
+}
+
+/// Power management mode of a client STA, analogous to `PowerManagementMode` in the cyw43 control
+/// layer. Selected per-interface and toggled via `Client::set_power_management_mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerManagementMode {
+    /// The STA stays awake at all times; the PM bit is always clear on outbound frames.
+    PerformanceMode,
+    /// The STA may doze, relying on the TIM element in beacons to learn when the AP has buffered
+    /// frames for it. Selecting this mode does not by itself put the STA to sleep: dozing starts
+    /// only when `Client::enter_doze` is called.
+    PowerSaveMode,
+}
+
+/// WMM/EDCA access categories, IEEE Std 802.11-2016, Table 10-1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessCategory {
+    Background,
+    BestEffort,
+    Video,
+    Voice,
+}
+
+impl AccessCategory {
+    /// Returns a single TID representative of this access category, written into the QoS
+    /// Control field's TID subfield. Any of the TIDs mapping to the AC would do; these are the
+    /// conventional choices used throughout the 802.11e/WMM ecosystem (0 for BE, 1 for BK, 4 for
+    /// VI, 6 for VO).
+    fn representative_tid(&self) -> u8 {
+        match self {
+            AccessCategory::BestEffort => 0,
+            AccessCategory::Background => 1,
+            AccessCategory::Video => 4,
+            AccessCategory::Voice => 6,
+        }
+    }
+}
+
+/// Maps an 802.11e User Priority (0-7) to its WMM access category, IEEE Std 802.11-2016,
+/// Table 10-1.
+fn user_priority_to_ac(user_priority: u8) -> AccessCategory {
+    match user_priority {
+        1 | 2 => AccessCategory::Background,
+        0 | 3 => AccessCategory::BestEffort,
+        4 | 5 => AccessCategory::Video,
+        6 | 7 => AccessCategory::Voice,
+        _ => AccessCategory::BestEffort,
+    }
+}
+
+/// Maps a 6-bit IP DSCP value to an 802.11e User Priority, following the standard 802.1D
+/// DSCP-to-UP mapping (the three most significant bits of the DSCP field, i.e. the former IP
+/// Precedence, select the priority).
+fn dscp_to_user_priority(dscp: u8) -> u8 {
+    dscp >> 3
+}
+
+/// Derives the 802.11e User Priority for an outbound frame from its Ethernet payload: reads the
+/// IP DS field when `ether_type` is IPv4/IPv6, or falls back to best-effort (UP 0) for everything
+/// else, including when the payload is shorter than the 2 bytes needed to reach the DS field. The
+/// rest of the IP header is not checked.
+fn classify_user_priority(ether_type: u16, payload: &[u8]) -> u8 {
+    const ETHER_TYPE_IPV4: u16 = 0x0800;
+    const ETHER_TYPE_IPV6: u16 = 0x86DD;
+    match ether_type {
+        ETHER_TYPE_IPV4 if payload.len() >= 2 => dscp_to_user_priority(payload[1] >> 2),
+        ETHER_TYPE_IPV6 if payload.len() >= 2 => {
+            let traffic_class = ((payload[0] & 0x0f) << 4) | (payload[1] >> 4);
+            dscp_to_user_priority(traffic_class >> 2)
+        }
+        _ => 0,
+    }
+}
+
+/// Fields pulled from a raw data MPDU's fixed header that drive EAPOL reassembly and Block Ack
+/// reordering in `Client::handle_data_frame`.
+struct DataFrameHdrInfo {
+    /// Sequence number, IEEE Std 802.11-2016, 9.4.1.4.
+    seq_num: u16,
+    /// Fragment number, IEEE Std 802.11-2016, 9.2.4.4 and 9.4.1.1.
+    frag_num: u8,
+    /// More Fragments bit, IEEE Std 802.11-2016, 9.2.4.1.3.
+    more_fragments: bool,
+    /// More Data bit, IEEE Std 802.11-2016, 9.2.4.1.4: set by the AP to indicate it holds
+    /// additional buffered frames for this STA, used to decide whether to keep PS-Polling.
+    more_data: bool,
+    /// TID carried in the QoS Control field, if this is a QoS data frame.
+    tid: Option<u8>,
+}
+
+/// Parses the fields above out of a raw data MPDU. Returns `None` if the frame is too short to
+/// contain a fixed data header.
+fn parse_data_frame_hdr_info<B: ByteSlice>(bytes: &B) -> Option<DataFrameHdrInfo> {
+    let (hdr, body) = mac::FixedDataHdrFields::parse(&bytes[..])?;
+    let is_qos = hdr.frame_ctrl.subtype() & 0b1000 != 0;
+    let tid = if is_qos { body.get(0).map(|b| b & 0x0f) } else { None };
+    Some(DataFrameHdrInfo {
+        seq_num: hdr.seq_ctrl.seq_num(),
+        frag_num: hdr.seq_ctrl.frag_num(),
+        more_fragments: hdr.frame_ctrl.more_fragments(),
+        more_data: hdr.frame_ctrl.more_data(),
+        tid,
+    })
+}
+
+/// Returns the transmitter address (Address 2) of a raw over-the-air MAC frame: the frame control
+/// and duration fields are followed by Address 1 then Address 2 at the same fixed offset in every
+/// management and data frame subtype (IEEE Std 802.11-2016, 9.3.3.2, 9.3.2.1). For any frame
+/// actually sent by the client's AP, this is the BSSID. Returns `None` if `bytes` is too short to
+/// contain it.
+fn frame_transmitter_addr<B: ByteSlice>(bytes: &B) -> Option<MacAddr> {
+    const ADDR2_OFFSET: usize = 2 /* frame control */ + 2 /* duration */ + 6 /* addr1 */;
+    let bytes = &bytes[..];
+    let addr2 = bytes.get(ADDR2_OFFSET..ADDR2_OFFSET + 6)?;
+    let mut addr = [0u8; 6];
+    addr.copy_from_slice(addr2);
+    Some(addr)
+}
+
+/// Checks whether the AP has buffered unicast traffic for `aid`, by decoding the Partial Virtual
+/// Bitmap of a received TIM element, IEEE Std 802.11-2016, 9.4.2.6 and Figure 9-371: the bitmap
+/// starts at an even octet offset given by the Bitmap Control field, and each bit within it
+/// corresponds to one AID.
+fn tim_bit_set_for_aid(tim: &wlan_common::ie::TimView<&[u8]>, aid: Aid) -> bool {
+    let bitmap_offset = (tim.header.bmp_ctrl.offset() as usize) * 2;
+    let octet = (aid as usize) / 8;
+    if octet < bitmap_offset {
+        return false;
+    }
+    match tim.bitmap.get(octet - bitmap_offset) {
+        Some(byte) => byte & (1 << (aid % 8)) != 0,
+        None => false,
+    }
+}
+
+/// Reason a received or outbound frame was silently discarded by the client station, recorded in
+/// `FrameDropCounters` so operators can diagnose dropped traffic via inspect/telemetry rather than
+/// only observing an empty queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDropReason {
+    /// A non-EAPoL MSDU arrived while the controlled port was still closed.
+    ControlledPortClosed,
+    /// A (re)assembled EAPOL PDU exceeded `ClientConfig::max_eapol_pdu_len`.
+    EapolPduTooLarge,
+    /// A data frame's fixed header parsed fine, but its body couldn't be decoded into any MSDU at
+    /// all, e.g. an A-MSDU subframe truncated before its padding or next subframe header.
+    MalformedAmsduPadding,
+    /// An MSDU's EtherType fell below 0x0600, the IEEE 802.3 boundary separating a valid EtherType
+    /// from a Length field, so it can't be forwarded as a well-formed Ethernet II frame.
+    UnknownEtherType,
+    /// A received MPDU's fixed header was too malformed to parse, e.g. too short for its declared
+    /// Frame Control.
+    BadFrameControl,
+}
+
+/// Running per-reason counts of frames this client station has discarded. See `FrameDropReason`
+/// and `Client::frame_drop_counts`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FrameDropCounters {
+    pub controlled_port_closed: u64,
+    pub eapol_pdu_too_large: u64,
+    pub malformed_amsdu_padding: u64,
+    pub unknown_ether_type: u64,
+    pub bad_frame_control: u64,
+}
+
+impl FrameDropCounters {
+    fn record(&mut self, reason: FrameDropReason) {
+        match reason {
+            FrameDropReason::ControlledPortClosed => self.controlled_port_closed += 1,
+            FrameDropReason::EapolPduTooLarge => self.eapol_pdu_too_large += 1,
+            FrameDropReason::MalformedAmsduPadding => self.malformed_amsdu_padding += 1,
+            FrameDropReason::UnknownEtherType => self.unknown_ether_type += 1,
+            FrameDropReason::BadFrameControl => self.bad_frame_control += 1,
+        }
+    }
 }
 
 /// ClientConfig affects time duration used for different timeouts.
@@ -45,6 +371,25 @@ pub enum TimedEvent {
 pub struct ClientConfig {
     signal_report_beacon_timeout: usize,
     ensure_on_channel_time: zx::sys::zx_duration_t,
+    /// Upper bound on the size of a fully-reassembled EAPOL PDU forwarded to SME. See
+    /// `DEFAULT_MAX_EAPOL_PDU_LEN`.
+    max_eapol_pdu_len: usize,
+    /// Upper bound on the size of an aggregated A-MSDU payload. See `DEFAULT_MAX_AMSDU_LEN`.
+    max_amsdu_len: usize,
+}
+
+/// Tracks beacon-loss detection state for the connection monitor. Only meaningful while the STA
+/// is associated; reset whenever any frame from the BSSID is observed.
+#[derive(Debug, Default)]
+struct ConnectionMonitor {
+    /// Beacon interval of the associated BSS, in TU, used to re-arm the recurring tick.
+    beacon_period_tu: u16,
+    /// Number of consecutive `ConnectionMonitor` ticks (i.e. beacon intervals) elapsed without a
+    /// beacon or any other frame heard from the BSSID.
+    missed_beacon_count: usize,
+    /// Set once a keep-alive probe has been sent in response to crossing the beacon-loss
+    /// threshold, while we wait to see if the BSSID responds before the probe timeout fires.
+    probe_outstanding: bool,
 }
 
 /// A STA running in Client mode.
@@ -58,6 +403,33 @@ pub struct Client {
     bssid: Bssid,
     iface_mac: MacAddr,
     state: Option<States>,
+    power_mgmt_mode: PowerManagementMode,
+    is_dozing: bool,
+    client_config: ClientConfig,
+    connection_monitor: ConnectionMonitor,
+    /// Whether Management Frame Protection was negotiated with the current BSS via the RSNE
+    /// exchanged in `send_assoc_req_frame`. While true, unprotected Deauthentication and
+    /// Disassociation frames are ignored rather than honored, and SA Query is used instead to
+    /// confirm a peer has actually lost its security association.
+    pmf_enabled: bool,
+    sa_query: Option<SaQueryState>,
+    /// In-progress EAPOL fragment reassemblies, keyed by the MSDU's (src_addr, dst_addr). See
+    /// `Client::handle_data_frame` and `EapolReassembly`.
+    eapol_reassembly: HashMap<(MacAddr, MacAddr), EapolReassembly>,
+    /// QoS MSDUs buffered per-TID awaiting A-MSDU aggregation. The STA always transmits to its
+    /// single associated BSSID, so the RA component of the usual (RA, TID) aggregation key is
+    /// constant and only the TID need be tracked. See `Client::enqueue_qos_msdu`.
+    amsdu_pending: HashMap<u8, Vec<PendingMsdu>>,
+    /// Per-(peer, TID) Block Ack receive reorder buffers. See `BaReorderBuffer` and
+    /// `Client::handle_inbound_msdu`.
+    ba_reorder: HashMap<(MacAddr, u8), BaReorderBuffer>,
+    /// Running per-reason counts of discarded frames. See `FrameDropCounters`.
+    frame_drop_counters: FrameDropCounters,
+    /// In-progress PS-Poll for AP-buffered traffic, if any. See `PsPollState`.
+    ps_poll: Option<PsPollState>,
+    /// Configured TID-to-RX-queue mapping. TIDs with no entry default to queue 0. See
+    /// `Client::set_tid_queue_mapping`.
+    tid_queue_map: HashMap<u8, RxQueueId>,
 }
 
 impl Client {
@@ -67,6 +439,7 @@ impl Client {
         scheduler: Scheduler,
         bssid: Bssid,
         iface_mac: MacAddr,
+        client_config: ClientConfig,
     ) -> Self {
         let timer = Timer::<TimedEvent>::new(scheduler);
         Self {
@@ -77,6 +450,203 @@ impl Client {
             bssid,
             iface_mac,
             state: Some(States::new_initial()),
+            power_mgmt_mode: PowerManagementMode::PerformanceMode,
+            is_dozing: false,
+            client_config,
+            connection_monitor: ConnectionMonitor::default(),
+            pmf_enabled: false,
+            sa_query: None,
+            eapol_reassembly: HashMap::new(),
+            amsdu_pending: HashMap::new(),
+            ba_reorder: HashMap::new(),
+            frame_drop_counters: FrameDropCounters::default(),
+            ps_poll: None,
+            tid_queue_map: HashMap::new(),
+        }
+    }
+
+    /// Returns the running per-reason counts of frames this client station has discarded, for
+    /// inspect/telemetry surfaces to report on.
+    pub fn frame_drop_counts(&self) -> FrameDropCounters {
+        self.frame_drop_counters
+    }
+
+    /// Starts a receive reorder buffer for a newly-negotiated Block Ack agreement with `peer` on
+    /// `tid`. Any MSDU already buffered for this (peer, TID) is discarded; this is only meant to
+    /// be called once, when the agreement is first established.
+    pub fn start_ba_reorder_buffer(&mut self, peer: MacAddr, tid: u8, starting_seq: u16) {
+        self.ba_reorder
+            .insert((peer, tid), BaReorderBuffer::new(starting_seq, DEFAULT_BA_WINDOW_SIZE));
+    }
+
+    /// Starts the connection monitor once the STA is associated, scheduling a recurring tick at
+    /// the BSS's beacon interval. Call again after a roam to a new BSSID.
+    pub fn start_connection_monitoring(&mut self, beacon_period_tu: u16) {
+        self.connection_monitor = ConnectionMonitor { beacon_period_tu, ..Default::default() };
+        let beacon_period = zx::Duration::from_millis(beacon_period_tu as i64 * 1024 / 1000);
+        self.timer.schedule_event(beacon_period, TimedEvent::ConnectionMonitor);
+    }
+
+    /// Called whenever any frame from the associated BSSID is observed in `on_mac_frame`. Resets
+    /// the beacon-loss counters so a healthy connection never accumulates misses across frames
+    /// that aren't beacons (e.g. data frames from the AP also count as liveness).
+    pub fn on_bssid_frame_seen(&mut self) {
+        self.connection_monitor.missed_beacon_count = 0;
+        self.connection_monitor.probe_outstanding = false;
+    }
+
+    /// Drives the beacon-loss state machine on each `ConnectionMonitor` timer tick. Counts a
+    /// missed beacon interval; once `signal_report_beacon_timeout` consecutive intervals have
+    /// elapsed with nothing heard from the BSSID, sends a directed keep-alive NULL data frame and
+    /// arms a short probe timeout. If the probe also goes unanswered (no frame resets the
+    /// counters before `ConnectionMonitorProbeTimeout` fires), the connection is torn down.
+    fn handle_connection_monitor_tick(&mut self) {
+        let beacon_period_tu = self.connection_monitor.beacon_period_tu;
+        self.connection_monitor.missed_beacon_count += 1;
+        if self.connection_monitor.missed_beacon_count < self.client_config.signal_report_beacon_timeout
+        {
+            self.timer.schedule_event(
+                zx::Duration::from_millis(beacon_period_tu as i64 * 1024 / 1000),
+                TimedEvent::ConnectionMonitor,
+            );
+            return;
+        }
+
+        if self.connection_monitor.probe_outstanding {
+            // The keep-alive probe went unanswered: the AP is gone.
+            if let Err(e) = self.send_deauth_frame(mac::ReasonCode::AP_INITIATED) {
+                error!("error sending deauthenticate frame after beacon loss: {}", e);
+            }
+            self.send_deauthenticate_ind(fidl_mlme::ReasonCode::LeavingNetworkDisassoc);
+            return;
+        }
+
+        self.connection_monitor.probe_outstanding = true;
+        if let Err(e) = self.send_keep_alive_resp_frame() {
+            error!("error sending beacon-loss keep-alive probe: {}", e);
+        }
+        self.timer.schedule_event(
+            zx::Duration::from_millis(beacon_period_tu as i64 * 1024 / 1000),
+            TimedEvent::ConnectionMonitorProbeTimeout,
+        );
+    }
+
+    /// Selects the STA's power management mode. Switching into `PowerSaveMode` only records the
+    /// mode: the STA does not doze until `enter_doze` is called, and `Client` never calls it on its
+    /// own when idle. Switching into `PerformanceMode` wakes the STA immediately if it was dozing.
+    pub fn set_power_management_mode(&mut self, mode: PowerManagementMode) {
+        self.power_mgmt_mode = mode;
+        if mode == PowerManagementMode::PerformanceMode && self.is_dozing {
+            if let Err(e) = self.exit_doze() {
+                error!("error waking from doze: {}", e);
+            }
+        }
+    }
+
+    /// Returns whether the STA is currently dozing.
+    pub fn is_dozing(&self) -> bool {
+        self.is_dozing
+    }
+
+    /// Transmits a NULL data frame with the Power Management bit set and marks the STA as
+    /// dozing. No-op if the STA is already dozing.
+    pub fn enter_doze(&mut self) -> Result<(), Error> {
+        if self.is_dozing {
+            return Ok(());
+        }
+        self.is_dozing = true;
+        self.send_power_state_null_frame()
+    }
+
+    /// Transmits a NULL data frame with the Power Management bit cleared and marks the STA as
+    /// awake. No-op if the STA is already awake.
+    pub fn exit_doze(&mut self) -> Result<(), Error> {
+        if !self.is_dozing {
+            return Ok(());
+        }
+        self.is_dozing = false;
+        self.send_power_state_null_frame()
+    }
+
+    /// Sends a NULL data frame whose Power Management bit reflects `self.is_dozing`, used to
+    /// announce a doze/wake transition to the AP.
+    fn send_power_state_null_frame(&mut self) -> Result<(), Error> {
+        const FRAME_LEN: usize = frame_len!(mac::FixedDataHdrFields);
+        let mut buf = self.buf_provider.get_buffer(FRAME_LEN)?;
+        let mut w = BufferWriter::new(&mut buf[..]);
+        write_power_state_frame(
+            &mut w,
+            self.bssid,
+            self.iface_mac,
+            &mut self.seq_mgr,
+            self.is_dozing,
+        )?;
+        let bytes_written = w.bytes_written();
+        let out_buf = OutBuf::from(buf, bytes_written);
+        self.device
+            .send_wlan_frame(out_buf, TxFlags::NONE)
+            .map_err(|s| Error::Status(format!("error sending power state null frame"), s))
+    }
+
+    /// Called whenever a beacon is received from the associated BSS while the STA is dozing.
+    /// Decodes the TIM element's Partial Virtual Bitmap and, if the bit for our AID is set,
+    /// starts an active polling loop that keeps issuing PS-Poll frames until the AP clears the
+    /// More-Data bit in a response, IEEE Std 802.11-2016, 11.2.3.
+    pub fn handle_beacon_tim(&mut self, aid: Aid, tim: &wlan_common::ie::TimView<&[u8]>) {
+        if !self.is_dozing || self.ps_poll.is_some() {
+            return;
+        }
+        if !tim_bit_set_for_aid(tim, aid) {
+            return;
+        }
+        self.ps_poll = Some(PsPollState { aid, retries_remaining: PS_POLL_MAX_RETRIES });
+        if let Err(e) = self.send_ps_poll_frame(aid) {
+            error!("error sending PS-Poll frame: {}", e);
+        }
+        self.timer.schedule_event(
+            zx::Duration::from_nanos(PS_POLL_RETRY_TIMEOUT),
+            TimedEvent::PsPollRetry(aid),
+        );
+    }
+
+    /// Drives PS-Poll retransmission on `TimedEvent::PsPollRetry`. No-op if the poll was already
+    /// resolved by a response (see `Client::handle_ps_poll_progress`) or replaced by a newer poll.
+    fn handle_ps_poll_retry(&mut self, aid: Aid) {
+        let retries_remaining = match &self.ps_poll {
+            Some(state) if state.aid == aid => state.retries_remaining,
+            _ => return,
+        };
+        if retries_remaining == 0 {
+            self.ps_poll = None;
+            return;
+        }
+        if let Some(state) = &mut self.ps_poll {
+            state.retries_remaining -= 1;
+        }
+        if let Err(e) = self.send_ps_poll_frame(aid) {
+            error!("error sending PS-Poll frame: {}", e);
+        }
+        self.timer.schedule_event(
+            zx::Duration::from_nanos(PS_POLL_RETRY_TIMEOUT),
+            TimedEvent::PsPollRetry(aid),
+        );
+    }
+
+    /// Called for every data frame received from the BSSID while a PS-Poll is in progress, to
+    /// decide whether to keep polling or consider the buffered traffic drained. If the frame's
+    /// More Data bit is still set, another PS-Poll is issued immediately for the next buffered
+    /// frame; otherwise the poll concludes and the STA goes back to simply dozing.
+    fn handle_ps_poll_progress(&mut self, more_data: bool) {
+        let aid = match &self.ps_poll {
+            Some(state) => state.aid,
+            None => return,
+        };
+        if !more_data {
+            self.ps_poll = None;
+            return;
+        }
+        if let Err(e) = self.send_ps_poll_frame(aid) {
+            error!("error sending PS-Poll frame: {}", e);
         }
     }
 
@@ -112,6 +682,12 @@ impl Client {
         has_padding: bool,
         is_controlled_port_open: bool,
     ) {
+        let hdr_info = parse_data_frame_hdr_info(&bytes);
+        if self.is_dozing {
+            if let Some(info) = &hdr_info {
+                self.handle_ps_poll_progress(info.more_data);
+            }
+        }
         if let Some(msdus) = mac::MsduIterator::from_raw_data_frame(bytes, has_padding) {
             match msdus {
                 // Handle NULL data frames independent of the controlled port's status.
@@ -126,35 +702,165 @@ impl Client {
                         let mac::Msdu { dst_addr, src_addr, llc_frame } = &msdu;
                         match llc_frame.hdr.protocol_id.to_native() {
                             // Forward EAPoL frames to SME independent of the controlled port's
-                            // status.
+                            // status, reassembling across 802.11 fragments first.
                             mac::ETHER_TYPE_EAPOL => {
-                                if let Err(e) = self.send_eapol_indication(
+                                let (frag_num, more_fragments) = hdr_info
+                                    .as_ref()
+                                    .map(|info| (info.frag_num, info.more_fragments))
+                                    .unwrap_or((0, false));
+                                self.handle_eapol_msdu(
                                     *src_addr,
                                     *dst_addr,
+                                    frag_num,
+                                    more_fragments,
                                     &llc_frame.body[..],
-                                ) {
-                                    error!("error sending MLME-EAPOL.indication: {}", e);
-                                }
+                                );
+                            }
+                            // IEEE Std 802.3-2018, 3.2.6: values below 0x0600 are reserved for the
+                            // 802.3 Length field, not a valid EtherType, so the MSDU can't be
+                            // forwarded as a well-formed Ethernet II frame.
+                            ether_type if ether_type < 0x0600 => {
+                                self.frame_drop_counters.record(FrameDropReason::UnknownEtherType);
                             }
-                            // Deliver non-EAPoL MSDUs only if the controlled port is open.
+                            // Deliver non-EAPoL MSDUs only if the controlled port is open,
+                            // routing through the peer/TID's Block Ack reorder buffer if one is
+                            // active.
                             _ if is_controlled_port_open => {
-                                if let Err(e) = self.deliver_msdu(msdu) {
-                                    error!("error while handling data frame: {}", e);
+                                let seq_num = hdr_info.as_ref().map(|info| info.seq_num);
+                                let tid = hdr_info.as_ref().and_then(|info| info.tid);
+                                match seq_num {
+                                    Some(seq_num) => self.handle_inbound_msdu(seq_num, tid, msdu),
+                                    None => {
+                                        if let Err(e) = self.deliver_msdu(msdu) {
+                                            error!("error while handling data frame: {}", e);
+                                        }
+                                    }
                                 }
                             }
                             // Drop all non-EAPoL MSDUs if the controlled port is closed.
-                            _ => (),
+                            _ => {
+                                self.frame_drop_counters
+                                    .record(FrameDropReason::ControlledPortClosed);
+                            }
                         }
                     }
                 }
             }
+        } else if hdr_info.is_some() {
+            // The fixed header parsed fine, but the body couldn't be decoded into any MSDU at
+            // all, e.g. a truncated A-MSDU subframe.
+            self.frame_drop_counters.record(FrameDropReason::MalformedAmsduPadding);
+        } else {
+            // The frame's fixed header itself couldn't be parsed.
+            self.frame_drop_counters.record(FrameDropReason::BadFrameControl);
+        }
+    }
+
+    /// Handles a single EAPOL MSDU extracted from a (possibly fragmented) data frame, reassembling
+    /// it with any sibling fragments keyed on (src_addr, dst_addr) before forwarding a completed
+    /// EAPOL PDU to SME. See `EapolReassembly`.
+    fn handle_eapol_msdu(
+        &mut self,
+        src_addr: MacAddr,
+        dst_addr: MacAddr,
+        frag_num: u8,
+        more_fragments: bool,
+        fragment: &[u8],
+    ) {
+        // The common case: a single-fragment EAPOL frame. Skip the reassembly buffer entirely.
+        if frag_num == 0 && !more_fragments {
+            self.eapol_reassembly.remove(&(src_addr, dst_addr));
+            self.forward_complete_eapol_pdu(src_addr, dst_addr, fragment);
+            return;
+        }
+
+        let key = (src_addr, dst_addr);
+        if frag_num == 0 {
+            self.eapol_reassembly
+                .insert(key, EapolReassembly { next_frag_num: 1, buf: fragment.to_vec() });
+            self.timer.schedule_event(
+                zx::Duration::from_nanos(EAPOL_REASSEMBLY_TIMEOUT),
+                TimedEvent::EapolReassemblyTimeout(src_addr, dst_addr),
+            );
+            return;
+        }
+
+        let max_len = self.client_config.max_eapol_pdu_len;
+        let complete = match self.eapol_reassembly.get_mut(&key) {
+            Some(reassembly) if reassembly.next_frag_num == frag_num => {
+                reassembly.buf.extend_from_slice(fragment);
+                if reassembly.buf.len() > max_len {
+                    error!(
+                        "EAPOL reassembly from {:?} exceeded {} bytes; dropping",
+                        src_addr, max_len
+                    );
+                    self.eapol_reassembly.remove(&key);
+                    self.frame_drop_counters.record(FrameDropReason::EapolPduTooLarge);
+                    return;
+                }
+                reassembly.next_frag_num += 1;
+                !more_fragments
+            }
+            // Out-of-order or gapped fragment: the sequence can no longer be trusted.
+            Some(_) => {
+                error!(
+                    "out-of-order EAPOL fragment from {:?}; discarding partial reassembly",
+                    src_addr
+                );
+                self.eapol_reassembly.remove(&key);
+                return;
+            }
+            // No reassembly in progress for a non-initial fragment: drop it.
+            None => return,
+        };
+
+        if complete {
+            if let Some(reassembly) = self.eapol_reassembly.remove(&key) {
+                self.forward_complete_eapol_pdu(src_addr, dst_addr, &reassembly.buf[..]);
+            }
+        }
+    }
+
+    /// Forwards a fully reassembled EAPOL PDU to SME, first verifying its 4-byte EAPOL header
+    /// declares a body length consistent with what was actually reassembled, so a truncated or
+    /// mis-terminated fragment sequence isn't delivered as if complete.
+    fn forward_complete_eapol_pdu(&mut self, src_addr: MacAddr, dst_addr: MacAddr, pdu: &[u8]) {
+        if pdu.len() < 4 {
+            error!("EAPOL PDU from {:?} shorter than its own header; dropping", src_addr);
+            return;
+        }
+        let declared_len = 4 + u16::from_be_bytes([pdu[2], pdu[3]]) as usize;
+        if pdu.len() < declared_len {
+            error!(
+                "EAPOL PDU from {:?} shorter ({} bytes) than its header declares ({} bytes); dropping",
+                src_addr,
+                pdu.len(),
+                declared_len
+            );
+            return;
         }
+        if let Err(e) = self.send_eapol_indication(src_addr, dst_addr, pdu) {
+            error!("error sending MLME-EAPOL.indication: {}", e);
+        }
+    }
+
+    /// Drops an EAPOL reassembly that didn't complete within `EAPOL_REASSEMBLY_TIMEOUT`.
+    fn handle_eapol_reassembly_timeout(&mut self, src_addr: MacAddr, dst_addr: MacAddr) {
+        self.eapol_reassembly.remove(&(src_addr, dst_addr));
     }
 
     /// Delivers a single MSDU to the STA's underlying device. The MSDU is delivered as an
     /// Ethernet II frame.
     /// Returns Err(_) if writing or delivering the Ethernet II frame failed.
     fn deliver_msdu<B: ByteSlice>(&mut self, msdu: mac::Msdu<B>) -> Result<(), Error> {
+        let bytes = Self::eth_frame_bytes_for_msdu(msdu)?;
+        self.deliver_eth_frame_bytes(&bytes[..])
+    }
+
+    /// Renders a single MSDU as an owned, standalone Ethernet II frame, independent of the
+    /// lifetime of the MPDU it was extracted from. Used to hold MSDUs in a `BaReorderBuffer`
+    /// until they can be delivered in order.
+    fn eth_frame_bytes_for_msdu<B: ByteSlice>(msdu: mac::Msdu<B>) -> Result<Vec<u8>, Error> {
         let mac::Msdu { dst_addr, src_addr, llc_frame } = msdu;
 
         let mut buf = [0u8; mac::MAX_ETH_FRAME_LEN];
@@ -166,11 +872,193 @@ impl Client {
             llc_frame.hdr.protocol_id.to_native(),
             &llc_frame.body,
         )?;
+        Ok(writer.into_written().to_vec())
+    }
+
+    /// Delivers an already-rendered Ethernet II frame to the STA's underlying device, on the
+    /// single default RX queue. Used by `deliver_msdu` for frames whose header couldn't be parsed,
+    /// which leaves no TID to steer by.
+    fn deliver_eth_frame_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.deliver_eth_frame_bytes_on_queue(None, bytes)
+    }
+
+    /// Delivers an already-rendered Ethernet II frame to the RX queue configured for `tid`, so
+    /// latency-sensitive access categories can be serviced independently of bulk traffic. See
+    /// `Client::set_tid_queue_mapping`.
+    fn deliver_eth_frame_bytes_on_queue(
+        &mut self,
+        tid: Option<u8>,
+        bytes: &[u8],
+    ) -> Result<(), Error> {
+        let queue = self.queue_for_tid(tid);
         self.device
-            .deliver_eth_frame(writer.into_written())
+            .deliver_eth_frame_on_queue(queue, bytes)
             .map_err(|s| Error::Status(format!("could not deliver Ethernet II frame"), s))
     }
 
+    /// Maps a QoS TID to the RX queue its MSDUs should be delivered on. TIDs without an explicit
+    /// mapping default to queue 0, preserving single-queue behavior.
+    fn queue_for_tid(&self, tid: Option<u8>) -> RxQueueId {
+        tid.and_then(|tid| self.tid_queue_map.get(&tid).copied()).unwrap_or(0)
+    }
+
+    /// Configures which RX queue MSDUs carrying `tid` are delivered to the device on. Call
+    /// repeatedly to build up a full TID-to-queue mapping; unmapped TIDs keep using queue 0.
+    pub fn set_tid_queue_mapping(&mut self, tid: u8, queue: RxQueueId) {
+        self.tid_queue_map.insert(tid, queue);
+    }
+
+    /// Handles a single non-EAPOL MSDU once the controlled port is known to be open, routing it
+    /// through the peer/TID's Block Ack reorder buffer (if one is active) before delivery.
+    fn handle_inbound_msdu<B: ByteSlice>(
+        &mut self,
+        seq_num: u16,
+        tid: Option<u8>,
+        msdu: mac::Msdu<B>,
+    ) {
+        let peer = msdu.src_addr;
+        let key = tid.map(|tid| (peer, tid));
+        let buffered = key.and_then(|key| self.ba_reorder.contains_key(&key).then(|| key));
+        let bytes = match Self::eth_frame_bytes_for_msdu(msdu) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                error!("error while handling data frame: {}", e);
+                return;
+            }
+        };
+        match buffered {
+            Some(key) => self.reorder_and_deliver(key, seq_num, bytes),
+            None => {
+                if let Err(e) = self.deliver_eth_frame_bytes_on_queue(tid, &bytes[..]) {
+                    error!("error while handling data frame: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Feeds a single MSDU, already rendered to Ethernet frame bytes, into the Block Ack reorder
+    /// buffer identified by `key`, delivering it immediately if it fills the base of the window,
+    /// buffering it otherwise, IEEE Std 802.11-2016, 10.24.
+    fn reorder_and_deliver(&mut self, key: (MacAddr, u8), seq_num: u16, bytes: Vec<u8>) {
+        let buffer = match self.ba_reorder.get_mut(&key) {
+            Some(buffer) => buffer,
+            None => return,
+        };
+        let offset = seq_num.wrapping_sub(buffer.base_seq) % SEQ_NUM_MODULUS;
+        if offset >= SEQ_NUM_MODULUS / 2 {
+            // Sequence number is behind the window, i.e. a duplicate or a very late retransmit.
+            // Drop it rather than reordering backwards.
+            return;
+        }
+        if offset >= buffer.window_size {
+            // Ahead of the window: the sender has moved on without us ever filling the hole at
+            // the base. Flush what's held, deliver this frame directly, and advance the base
+            // past it.
+            let next_base = seq_num.wrapping_add(1) % SEQ_NUM_MODULUS;
+            self.advance_ba_base(key, seq_num);
+            if let Err(e) = self.deliver_eth_frame_bytes_on_queue(Some(key.1), &bytes[..]) {
+                error!("error while handling data frame: {}", e);
+            }
+            if let Some(buffer) = self.ba_reorder.get_mut(&key) {
+                buffer.base_seq = next_base;
+            }
+            return;
+        }
+        if offset == 0 {
+            let next_base = buffer.base_seq.wrapping_add(1) % SEQ_NUM_MODULUS;
+            if let Err(e) = self.deliver_eth_frame_bytes_on_queue(Some(key.1), &bytes[..]) {
+                error!("error while handling data frame: {}", e);
+            }
+            self.advance_ba_base(key, next_base);
+        } else {
+            buffer.held.insert(offset, bytes);
+            self.timer.schedule_event(
+                zx::Duration::from_nanos(BA_REORDER_TIMEOUT),
+                TimedEvent::BaReorderTimeout(key.0, key.1),
+            );
+        }
+    }
+
+    /// Advances a Block Ack reorder buffer's base sequence number to `new_base`, delivering any
+    /// held MSDUs that are now contiguous from the old base and re-indexing everything still held
+    /// relative to the new base.
+    fn advance_ba_base(&mut self, key: (MacAddr, u8), new_base: u16) {
+        let buffer = match self.ba_reorder.get_mut(&key) {
+            Some(buffer) => buffer,
+            None => return,
+        };
+        let shift = new_base.wrapping_sub(buffer.base_seq) % SEQ_NUM_MODULUS;
+        let mut held = std::mem::replace(&mut buffer.held, HashMap::new());
+        buffer.base_seq = new_base;
+
+        let mut to_deliver = Vec::new();
+        let mut reindexed = HashMap::new();
+        for (offset, bytes) in held.drain() {
+            if offset < shift {
+                to_deliver.push((offset, bytes));
+            } else {
+                reindexed.insert(offset - shift, bytes);
+            }
+        }
+        to_deliver.sort_by_key(|(offset, _)| *offset);
+        if let Some(buffer) = self.ba_reorder.get_mut(&key) {
+            buffer.held = reindexed;
+        }
+        for (_, bytes) in to_deliver {
+            if let Err(e) = self.deliver_eth_frame_bytes_on_queue(Some(key.1), &bytes[..]) {
+                error!("error while handling data frame: {}", e);
+            }
+        }
+
+        // Deliver and advance past any further MSDUs that are now contiguous at the new base.
+        loop {
+            let buffer = match self.ba_reorder.get_mut(&key) {
+                Some(buffer) => buffer,
+                None => return,
+            };
+            match buffer.held.remove(&0) {
+                Some(bytes) => {
+                    let next_base = buffer.base_seq.wrapping_add(1) % SEQ_NUM_MODULUS;
+                    if let Err(e) = self.deliver_eth_frame_bytes_on_queue(Some(key.1), &bytes[..]) {
+                        error!("error while handling data frame: {}", e);
+                    }
+                    if let Some(buffer) = self.ba_reorder.get_mut(&key) {
+                        let mut shifted = HashMap::new();
+                        for (offset, bytes) in buffer.held.drain() {
+                            shifted.insert(offset - 1, bytes);
+                        }
+                        buffer.held = shifted;
+                        buffer.base_seq = next_base;
+                    }
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// Releases a stuck hole in a Block Ack reorder buffer: rather than stalling the stream
+    /// indefinitely, delivers everything held, in sequence order, and resets the base past them.
+    fn handle_ba_reorder_timeout(&mut self, peer: MacAddr, tid: u8) {
+        let key = (peer, tid);
+        let buffer = match self.ba_reorder.get_mut(&key) {
+            Some(buffer) => buffer,
+            None => return,
+        };
+        if buffer.held.is_empty() {
+            return;
+        }
+        let mut held: Vec<(u16, Vec<u8>)> = buffer.held.drain().collect();
+        held.sort_by_key(|(offset, _)| *offset);
+        let last_offset = held.last().map(|(offset, _)| *offset).unwrap_or(0);
+        let new_base = buffer.base_seq.wrapping_add(last_offset + 1) % SEQ_NUM_MODULUS;
+        buffer.base_seq = new_base;
+        for (_, bytes) in held {
+            if let Err(e) = self.deliver_eth_frame_bytes_on_queue(Some(tid), &bytes[..]) {
+                error!("error while handling data frame: {}", e);
+            }
+        }
+    }
+
     /// Sends an authentication frame using Open System authentication.
     pub fn send_open_auth_frame(&mut self) -> Result<(), Error> {
         const FRAME_LEN: usize = frame_len!(mac::MgmtHdr, mac::AuthHdr);
@@ -220,6 +1108,16 @@ impl Client {
         let vht_cap =
             if vht_cap.is_empty() { None } else { Some(*parse_vht_capabilities(vht_cap)?) };
 
+        // Management Frame Protection is negotiated through the RSN Capabilities subfield of the
+        // RSNE we're about to send. If either "capable" or "required" is set, treat PMF as
+        // enabled for the duration of this association: unprotected deauth/disassoc are no longer
+        // trusted and the SA Query responder/initiator become active.
+        self.pmf_enabled = rsne.as_ref().map_or(false, |r| {
+            r.rsn_capabilities.as_ref().map_or(false, |caps| {
+                caps.mgmt_frame_protection_cap() || caps.mgmt_frame_protection_req()
+            })
+        });
+
         write_assoc_req_frame(
             &mut w,
             self.bssid,
@@ -269,7 +1167,13 @@ impl Client {
             .map_err(|s| Error::Status(format!("error sending deauthenticate frame"), s))
     }
 
-    /// Sends the given payload as a data frame over the air.
+    /// Sends the given payload as a data frame over the air. When `is_qos` is true, the frame's
+    /// TID is derived from `priority` if given, or else from the IP DSCP field of `payload` when
+    /// `ether_type` is IPv4/IPv6; otherwise the frame falls back to TID 0 (AC_BE).
+    ///
+    /// Non-EAPOL QoS MSDUs are not necessarily sent immediately: they are buffered per-TID for a
+    /// short aggregation window so that frames destined to the AP in quick succession can be
+    /// coalesced into a single A-MSDU. See `enqueue_qos_msdu`.
     pub fn send_data_frame(
         &mut self,
         src: MacAddr,
@@ -277,6 +1181,102 @@ impl Client {
         is_protected: bool,
         is_qos: bool,
         ether_type: u16,
+        priority: Option<u8>,
+        payload: &[u8],
+    ) -> Result<(), Error> {
+        // EAPOL exchanges must complete reliably, so the STA always wakes for them rather than
+        // risking the frame being dropped or delayed behind a doze cycle.
+        if ether_type == mac::ETHER_TYPE_EAPOL && self.is_dozing {
+            self.exit_doze()?;
+        }
+        if !is_qos {
+            return self
+                .send_single_msdu_data_frame(src, dst, is_protected, false, 0, ether_type, payload);
+        }
+        let user_priority =
+            priority.unwrap_or_else(|| classify_user_priority(ether_type, payload));
+        let tid = user_priority_to_ac(user_priority).representative_tid();
+        // EAPOL frames bypass aggregation entirely; see the TODO in `send_eapol_frame` for why
+        // they're not even sent as QoS data frames in the first place.
+        if ether_type == mac::ETHER_TYPE_EAPOL {
+            return self.send_single_msdu_data_frame(
+                src, dst, is_protected, true, tid, ether_type, payload,
+            );
+        }
+        self.enqueue_qos_msdu(tid, src, dst, is_protected, ether_type, payload.to_vec());
+        Ok(())
+    }
+
+    /// Buffers a QoS MSDU for possible A-MSDU aggregation with others sharing `tid`. If adding it
+    /// would overflow `max_amsdu_len`, whatever is already pending is flushed first so the new
+    /// MSDU starts a fresh aggregate. The first MSDU to land in an empty per-TID buffer arms
+    /// `AMSDU_AGGREGATION_WINDOW`, after which everything buffered for `tid` is flushed as either
+    /// a single MSDU or an A-MSDU.
+    fn enqueue_qos_msdu(
+        &mut self,
+        tid: u8,
+        src: MacAddr,
+        dst: MacAddr,
+        is_protected: bool,
+        ether_type: u16,
+        payload: Vec<u8>,
+    ) {
+        let new_len = amsdu_subframe_len(&payload);
+        let pending_len: usize = self
+            .amsdu_pending
+            .get(&tid)
+            .map(|pending| pending.iter().map(|m| amsdu_subframe_len(&m.payload)).sum())
+            .unwrap_or(0);
+        if pending_len > 0 && pending_len + new_len > self.client_config.max_amsdu_len {
+            self.flush_amsdu(tid);
+        }
+        let pending = self.amsdu_pending.entry(tid).or_insert_with(Vec::new);
+        let was_empty = pending.is_empty();
+        pending.push(PendingMsdu { src, dst, is_protected, ether_type, payload });
+        if was_empty {
+            self.timer.schedule_event(
+                zx::Duration::from_nanos(AMSDU_AGGREGATION_WINDOW),
+                TimedEvent::AmsduFlush(tid),
+            );
+        }
+    }
+
+    /// Flushes whatever is pending for `tid`: a lone buffered MSDU is sent as an ordinary QoS data
+    /// frame, while two or more are coalesced into a single A-MSDU.
+    fn flush_amsdu(&mut self, tid: u8) {
+        let pending = match self.amsdu_pending.remove(&tid) {
+            Some(pending) if !pending.is_empty() => pending,
+            _ => return,
+        };
+        let result = if pending.len() == 1 {
+            let msdu = pending.into_iter().next().unwrap();
+            self.send_single_msdu_data_frame(
+                msdu.src,
+                msdu.dst,
+                msdu.is_protected,
+                true,
+                tid,
+                msdu.ether_type,
+                &msdu.payload,
+            )
+        } else {
+            self.send_amsdu_data_frame(tid, &pending)
+        };
+        if let Err(e) = result {
+            error!("error flushing aggregated data frame(s) for TID {}: {}", tid, e);
+        }
+    }
+
+    /// Sends a single MSDU as one MPDU: either a non-QoS data frame, or a QoS data frame with the
+    /// A-MSDU-present bit clear.
+    fn send_single_msdu_data_frame(
+        &mut self,
+        src: MacAddr,
+        dst: MacAddr,
+        is_protected: bool,
+        is_qos: bool,
+        tid: u8,
+        ether_type: u16,
         payload: &[u8],
     ) -> Result<(), Error> {
         let qos_presence = Presence::from_bool(is_qos);
@@ -293,6 +1293,8 @@ impl Client {
             dst,
             is_protected,
             is_qos,
+            tid,
+            self.is_dozing,
             ether_type,
             payload,
         )?;
@@ -307,6 +1309,46 @@ impl Client {
             .map_err(|s| Error::Status(format!("error sending data frame"), s))
     }
 
+    /// Coalesces two or more buffered MSDUs sharing `tid` into a single QoS data frame whose
+    /// payload is a sequence of A-MSDU subframes (`DA | SA | Length | LLC/SNAP | MSDU`, each
+    /// zero-padded to a 4-byte boundary except the last), with the A-MSDU-present bit set in the
+    /// QoS Control field. The aggregate is marked protected if any constituent MSDU is.
+    fn send_amsdu_data_frame(&mut self, tid: u8, msdus: &[PendingMsdu]) -> Result<(), Error> {
+        let is_protected = msdus.iter().any(|m| m.is_protected);
+        let data_hdr_len = mac::FixedDataHdrFields::len(
+            mac::Addr4::ABSENT,
+            Presence::Present,
+            mac::HtControl::ABSENT,
+        );
+        let frame_len =
+            data_hdr_len + msdus.iter().map(|m| amsdu_subframe_len(&m.payload)).sum::<usize>();
+        let mut buf = self.buf_provider.get_buffer(frame_len)?;
+        let mut w = BufferWriter::new(&mut buf[..]);
+        let subframes: Vec<AmsduSubframe<'_>> = msdus
+            .iter()
+            .map(|m| AmsduSubframe {
+                da: m.dst,
+                sa: m.src,
+                ether_type: m.ether_type,
+                payload: &m.payload[..],
+            })
+            .collect();
+        write_amsdu_data_frame(
+            &mut w,
+            &mut self.seq_mgr,
+            self.bssid,
+            is_protected,
+            tid,
+            self.is_dozing,
+            &subframes[..],
+        )?;
+        let bytes_written = w.bytes_written();
+        let out_buf = OutBuf::from(buf, bytes_written);
+        self.device
+            .send_wlan_frame(out_buf, TxFlags::NONE)
+            .map_err(|s| Error::Status(format!("error sending aggregated data frame"), s))
+    }
+
     /// Sends an MLME-EAPOL.indication to MLME's SME peer.
     /// Note: MLME-EAPOL.indication is a custom Fuchsia primitive and not defined in IEEE 802.11.
     fn send_eapol_indication(
@@ -315,7 +1357,8 @@ impl Client {
         dst_addr: MacAddr,
         eapol_frame: &[u8],
     ) -> Result<(), Error> {
-        if eapol_frame.len() > MAX_EAPOL_FRAME_LEN {
+        if eapol_frame.len() > self.client_config.max_eapol_pdu_len {
+            self.frame_drop_counters.record(FrameDropReason::EapolPduTooLarge);
             return Err(Error::Internal(format_err!(
                 "EAPOL frame too large: {}",
                 eapol_frame.len()
@@ -347,6 +1390,7 @@ impl Client {
             is_protected,
             false, /* don't use QoS */
             mac::ETHER_TYPE_EAPOL,
+            None,
             eapol_frame,
         );
         let result_code = match result {
@@ -378,14 +1422,134 @@ impl Client {
             .map_err(|s| Error::Status(format!("error sending PS-Poll frame"), s))
     }
 
+    /// Returns whether an unprotected Deauthentication or Disassociation frame should be dropped
+    /// rather than honored. True only once PMF has been negotiated for the current association;
+    /// callers (the association state machine's mgmt-frame handling) must consult this before
+    /// acting on an unprotected deauth/disassoc.
+    pub fn should_drop_unprotected_mgmt_frame(&self, is_protected: bool) -> bool {
+        self.pmf_enabled && !is_protected
+    }
+
+    /// Handles a received, protected SA Query *request* Action frame (category 8, action 0) by
+    /// replying with an SA Query *response* (category 8, action 1) echoing the same transaction
+    /// identifier, per IEEE Std 802.11-2016, 11.13.2.
+    pub fn handle_sa_query_request(&mut self, transaction_id: u16) -> Result<(), Error> {
+        const FRAME_LEN: usize = frame_len!(mac::MgmtHdr, mac::SaQueryHdr);
+        let mut buf = self.buf_provider.get_buffer(FRAME_LEN)?;
+        let mut w = BufferWriter::new(&mut buf[..]);
+        write_sa_query_resp_frame(
+            &mut w,
+            self.bssid,
+            self.iface_mac,
+            transaction_id,
+            &mut self.seq_mgr,
+        )?;
+        let bytes_written = w.bytes_written();
+        let out_buf = OutBuf::from(buf, bytes_written);
+        self.device
+            .send_wlan_frame(out_buf, TxFlags::NONE)
+            .map_err(|s| Error::Status(format!("error sending SA Query response frame"), s))
+    }
+
+    /// Called when a deauth/disassoc arrives from a PMF-protected peer but is dropped per
+    /// `should_drop_unprotected_mgmt_frame`. Starts (or restarts) an SA Query exchange with the
+    /// BSS to confirm whether the security association has actually been lost.
+    pub fn start_sa_query(&mut self) -> Result<(), Error> {
+        let transaction_id = self.seq_mgr.next_sns1(&self.bssid.0) as u16;
+        self.sa_query = Some(SaQueryState { transaction_id, retries_remaining: SA_QUERY_MAX_RETRIES });
+        self.timer.schedule_event(
+            zx::Duration::from_nanos(SA_QUERY_RETRY_TIMEOUT),
+            TimedEvent::SaQueryRetry,
+        );
+        self.send_sa_query_request(transaction_id)
+    }
+
+    fn send_sa_query_request(&mut self, transaction_id: u16) -> Result<(), Error> {
+        const FRAME_LEN: usize = frame_len!(mac::MgmtHdr, mac::SaQueryHdr);
+        let mut buf = self.buf_provider.get_buffer(FRAME_LEN)?;
+        let mut w = BufferWriter::new(&mut buf[..]);
+        write_sa_query_req_frame(
+            &mut w,
+            self.bssid,
+            self.iface_mac,
+            transaction_id,
+            &mut self.seq_mgr,
+        )?;
+        let bytes_written = w.bytes_written();
+        let out_buf = OutBuf::from(buf, bytes_written);
+        self.device
+            .send_wlan_frame(out_buf, TxFlags::NONE)
+            .map_err(|s| Error::Status(format!("error sending SA Query request frame"), s))
+    }
+
+    /// Called when a protected SA Query response is received. Clears the outstanding SA Query if
+    /// the echoed transaction identifier matches the one we sent.
+    pub fn handle_sa_query_response(&mut self, transaction_id: u16) {
+        if let Some(state) = &self.sa_query {
+            if state.transaction_id == transaction_id {
+                self.sa_query = None;
+            }
+        }
+    }
+
+    /// Drives SA Query retransmission on `TimedEvent::SaQueryRetry`. Once retries are exhausted
+    /// without a matching response, the association is considered lost.
+    fn handle_sa_query_retry(&mut self) {
+        let (transaction_id, retries_remaining) = match &self.sa_query {
+            Some(state) => (state.transaction_id, state.retries_remaining),
+            None => return, // Resolved by a response already.
+        };
+        if retries_remaining == 0 {
+            self.sa_query = None;
+            self.send_deauthenticate_ind(fidl_mlme::ReasonCode::LeavingNetworkDisassoc);
+            return;
+        }
+        if let Some(state) = &mut self.sa_query {
+            state.retries_remaining -= 1;
+        }
+        if let Err(e) = self.send_sa_query_request(transaction_id) {
+            error!("error retransmitting SA Query request: {}", e);
+        }
+        self.timer.schedule_event(
+            zx::Duration::from_nanos(SA_QUERY_RETRY_TIMEOUT),
+            TimedEvent::SaQueryRetry,
+        );
+    }
+
     /// Called when a previously scheduled `TimedEvent` fired.
     pub fn handle_timed_event(&mut self, event_id: EventId) {
-        // Safe: |state| is never None and always replaced with Some(..).
-        self.state = Some(self.state.take().unwrap().on_timed_event(self, event_id));
+        match self.timer.triggered(&event_id) {
+            // The connection monitor's events are handled directly by `Client` rather than being
+            // routed through the association state machine, since beacon-loss detection applies
+            // uniformly across the associated states.
+            Some(TimedEvent::ConnectionMonitor) | Some(TimedEvent::ConnectionMonitorProbeTimeout) => {
+                self.handle_connection_monitor_tick()
+            }
+            Some(TimedEvent::SaQueryRetry) => self.handle_sa_query_retry(),
+            Some(TimedEvent::EapolReassemblyTimeout(src_addr, dst_addr)) => {
+                self.handle_eapol_reassembly_timeout(src_addr, dst_addr)
+            }
+            Some(TimedEvent::AmsduFlush(tid)) => self.flush_amsdu(tid),
+            Some(TimedEvent::BaReorderTimeout(peer, tid)) => {
+                self.handle_ba_reorder_timeout(peer, tid)
+            }
+            Some(TimedEvent::PsPollRetry(aid)) => self.handle_ps_poll_retry(aid),
+            _ => {
+                // Safe: |state| is never None and always replaced with Some(..).
+                self.state = Some(self.state.take().unwrap().on_timed_event(self, event_id));
+            }
+        }
     }
 
     /// Called when an arbitrary frame was received over the air.
     pub fn on_mac_frame<B: ByteSlice>(&mut self, bytes: B, body_aligned: bool) {
+        // Any frame from our BSSID, not just beacons, counts as liveness and resets the
+        // beacon-loss counters maintained by the connection monitor. Frames transmitted by other
+        // nearby devices on the same channel must not reset it, or a genuinely silent AP would
+        // never trip beacon-loss detection.
+        if frame_transmitter_addr(&bytes) == Some(self.bssid.0) {
+            self.on_bssid_frame_seen();
+        }
         // Safe: |state| is never None and always replaced with Some(..).
         self.state = Some(self.state.take().unwrap().on_mac_frame(self, bytes, body_aligned));
     }
@@ -429,9 +1593,19 @@ mod tests {
     const BSSID: Bssid = Bssid([6u8; 6]);
     const IFACE_MAC: MacAddr = [7u8; 6];
 
+    fn test_client_config() -> ClientConfig {
+        ClientConfig {
+            signal_report_beacon_timeout: 3,
+            ensure_on_channel_time: 0,
+            max_eapol_pdu_len: DEFAULT_MAX_EAPOL_PDU_LEN,
+            max_amsdu_len: DEFAULT_MAX_AMSDU_LEN,
+        }
+    }
+
     fn make_client_station(device: Device, scheduler: Scheduler) -> Client {
         let buf_provider = FakeBufferProvider::new();
-        let client = Client::new(device, buf_provider, scheduler, BSSID, IFACE_MAC);
+        let client =
+            Client::new(device, buf_provider, scheduler, BSSID, IFACE_MAC, test_client_config());
         client
     }
 
@@ -538,7 +1712,7 @@ mod tests {
         let mut client =
             make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
         client
-            .send_data_frame([2; 6], [3; 6], false, false, 0x1234, &payload[..])
+            .send_data_frame([2; 6], [3; 6], false, false, 0x1234, None, &payload[..])
             .expect("error delivering WLAN frame");
         assert_eq!(fake_device.wlan_queue.len(), 1);
         #[rustfmt::skip]
@@ -559,6 +1733,188 @@ mod tests {
         ][..]);
     }
 
+    #[test]
+    fn client_send_data_frame_qos_uses_caller_priority() {
+        let payload = vec![5; 8];
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client
+            .send_data_frame([2; 6], [3; 6], false, true, 0x1234, Some(7), &payload[..])
+            .expect("error delivering WLAN frame");
+        assert_eq!(AccessCategory::Voice.representative_tid(), 6);
+        client.flush_amsdu(AccessCategory::Voice.representative_tid());
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+        #[rustfmt::skip]
+        assert_eq!(&fake_device.wlan_queue[0].0[..], &[
+            // Data header:
+            0b1000_10_00, 0b0000000_1, // FC
+            0, 0, // Duration
+            6, 6, 6, 6, 6, 6, // addr1
+            2, 2, 2, 2, 2, 2, // addr2
+            3, 3, 3, 3, 3, 3, // addr3
+            0x10, 0, // Sequence Control
+            0b0000_0110, 0, // QoS Control: TID 6 (Voice)
+            // LLC header:
+            0xAA, 0xAA, 0x03, // DSAP, SSAP, Control
+            0, 0, 0, // OUI
+            0x12, 0x34, // Protocol ID
+            // Payload
+            5, 5, 5, 5, 5, 5, 5, 5,
+        ][..]);
+    }
+
+    #[test]
+    fn client_send_data_frame_qos_classifies_ipv4_dscp() {
+        // IPv4 header with DSCP set to the AF41 codepoint (0x22 << 2 == 0x88), which maps to
+        // User Priority 4 (Video) via the 802.1D DSCP-to-UP table.
+        let mut payload = vec![0x45, 0x88, 0, 0, 0, 0, 0, 0];
+        payload.extend_from_slice(&[9; 12]);
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client
+            .send_data_frame([2; 6], [3; 6], false, true, 0x0800, None, &payload[..])
+            .expect("error delivering WLAN frame");
+        assert_eq!(AccessCategory::Video.representative_tid(), 4);
+        client.flush_amsdu(AccessCategory::Video.representative_tid());
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+        assert_eq!(&fake_device.wlan_queue[0].0[24..26], &[0b0000_0100, 0][..]);
+    }
+
+    #[test]
+    fn client_send_data_frame_non_qos_ignores_priority() {
+        let payload = vec![5; 8];
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client
+            .send_data_frame([2; 6], [3; 6], false, false, 0x1234, Some(7), &payload[..])
+            .expect("error delivering WLAN frame");
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+        // No QoS Control field is present for a non-QoS data frame, regardless of `priority`.
+        assert_eq!(fake_device.wlan_queue[0].0.len(), 24 + 3 + 3 + 2 + payload.len());
+    }
+
+    #[test]
+    fn amsdu_aggregates_frames_sharing_a_tid() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client
+            .send_data_frame([2; 6], [3; 6], false, true, 0x1234, Some(0), &[5; 8])
+            .expect("error delivering WLAN frame");
+        client
+            .send_data_frame([2; 6], [4; 6], false, true, 0x1234, Some(0), &[6; 8])
+            .expect("error delivering WLAN frame");
+        // Neither MSDU is sent until the aggregation window elapses.
+        assert_eq!(fake_device.wlan_queue.len(), 0);
+
+        client.flush_amsdu(AccessCategory::BestEffort.representative_tid());
+        // Both MSDUs sharing TID 0 (AC_BE) were coalesced into a single MPDU.
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+    }
+
+    #[test]
+    fn amsdu_flushes_early_when_next_frame_would_overflow() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        let big_payload = vec![5; DEFAULT_MAX_AMSDU_LEN];
+        client
+            .send_data_frame([2; 6], [3; 6], false, true, 0x1234, Some(0), &big_payload[..])
+            .expect("error delivering WLAN frame");
+        // A second MSDU that would overflow `max_amsdu_len` forces the first to flush on its own,
+        // rather than being held for the rest of the aggregation window.
+        client
+            .send_data_frame([2; 6], [4; 6], false, true, 0x1234, Some(0), &[6; 8])
+            .expect("error delivering WLAN frame");
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+
+        client.flush_amsdu(AccessCategory::BestEffort.representative_tid());
+        assert_eq!(fake_device.wlan_queue.len(), 2);
+    }
+
+    #[test]
+    fn ba_reorder_buffers_out_of_order_msdu_until_hole_fills() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        let peer = [7; 6];
+        client.start_ba_reorder_buffer(peer, 0, 5);
+
+        // Seq 6 arrives before seq 5: held, not yet delivered.
+        client.reorder_and_deliver((peer, 0), 6, vec![6]);
+        assert_eq!(fake_device.eth_queue.len(), 0);
+
+        // Seq 5 fills the hole at the base: both frames are delivered in order, and the base
+        // advances past the now-contiguous seq 6 as well.
+        client.reorder_and_deliver((peer, 0), 5, vec![5]);
+        assert_eq!(fake_device.eth_queue.len(), 2);
+        assert_eq!(&fake_device.eth_queue[0][..], &[5][..]);
+        assert_eq!(&fake_device.eth_queue[1][..], &[6][..]);
+        assert_eq!(client.ba_reorder.get(&(peer, 0)).unwrap().base_seq, 7);
+    }
+
+    #[test]
+    fn ba_reorder_drops_msdu_before_window() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        let peer = [7; 6];
+        client.start_ba_reorder_buffer(peer, 0, 10);
+
+        // A duplicate/late retransmit of a frame already passed over is dropped, not redelivered.
+        client.reorder_and_deliver((peer, 0), 9, vec![9]);
+        assert_eq!(fake_device.eth_queue.len(), 0);
+        assert_eq!(client.ba_reorder.get(&(peer, 0)).unwrap().base_seq, 10);
+    }
+
+    #[test]
+    fn ba_reorder_timeout_releases_stuck_hole() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        let peer = [7; 6];
+        client.start_ba_reorder_buffer(peer, 0, 5);
+
+        // Seq 5 never arrives; seq 6 and 7 are held behind it.
+        client.reorder_and_deliver((peer, 0), 6, vec![6]);
+        client.reorder_and_deliver((peer, 0), 7, vec![7]);
+        assert_eq!(fake_device.eth_queue.len(), 0);
+
+        client.handle_ba_reorder_timeout(peer, 0);
+        assert_eq!(fake_device.eth_queue.len(), 2);
+        assert_eq!(&fake_device.eth_queue[0][..], &[6][..]);
+        assert_eq!(&fake_device.eth_queue[1][..], &[7][..]);
+        assert_eq!(client.ba_reorder.get(&(peer, 0)).unwrap().base_seq, 8);
+    }
+
+    #[test]
+    fn tid_queue_mapping_defaults_to_queue_zero_until_configured() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+
+        // All TIDs, and frames with no TID at all, default to queue 0.
+        assert_eq!(client.queue_for_tid(Some(6)), 0);
+        assert_eq!(client.queue_for_tid(None), 0);
+
+        client.set_tid_queue_mapping(6, 2);
+        assert_eq!(client.queue_for_tid(Some(6)), 2);
+        // An unrelated TID is unaffected by the mapping configured above.
+        assert_eq!(client.queue_for_tid(Some(0)), 0);
+    }
+
     #[test]
     fn client_send_deauthentication_notification() {
         let mut fake_device = FakeDevice::new();
@@ -675,6 +2031,9 @@ mod tests {
         ];
         expected_first_eth_frame.extend_from_slice(MSDU_1_PAYLOAD);
         assert_eq!(queue[0], &expected_first_eth_frame[..]);
+        // The first subframe decoded and was delivered above; no drop reason should have fired
+        // for it.
+        assert_eq!(client.frame_drop_counts().malformed_amsdu_padding, 0);
     }
 
     #[test]
@@ -686,8 +2045,9 @@ mod tests {
             make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
         client.handle_data_frame(&data_frame[..], false, false);
 
-        // Verify frame was not sent to netstack.
+        // Verify frame was not sent to netstack, and the drop was recorded with a reason.
         assert_eq!(fake_device.eth_queue.len(), 0);
+        assert_eq!(client.frame_drop_counts().controlled_port_closed, 1);
     }
 
     #[test]
@@ -734,6 +2094,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn eapol_fragments_reassemble_and_forward() {
+        // A minimal well-formed EAPOL PDU: 4-byte header (version, type, 2-byte big-endian body
+        // length) declaring a 4-byte body, split across two 802.11 fragments.
+        let pdu = [1, 3, 0, 4, 0xAA, 0xBB, 0xCC, 0xDD];
+        let src_addr = [1; 6];
+        let dst_addr = [2; 6];
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+
+        client.handle_eapol_msdu(src_addr, dst_addr, 0, true, &pdu[..5]);
+        fake_device
+            .next_mlme_msg::<fidl_mlme::EapolIndication>()
+            .expect_err("reassembly isn't complete yet");
+
+        client.handle_eapol_msdu(src_addr, dst_addr, 1, false, &pdu[5..]);
+        let eapol_ind = fake_device
+            .next_mlme_msg::<fidl_mlme::EapolIndication>()
+            .expect("error reading EAPOL.indication");
+        assert_eq!(
+            eapol_ind,
+            fidl_mlme::EapolIndication { src_addr, dst_addr, data: pdu.to_vec() }
+        );
+    }
+
+    #[test]
+    fn eapol_reassembly_timeout_drops_partial_state() {
+        let src_addr = [1; 6];
+        let dst_addr = [2; 6];
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+
+        client.handle_eapol_msdu(src_addr, dst_addr, 0, true, &[1, 3, 0, 4, 0xAA]);
+        client.handle_eapol_reassembly_timeout(src_addr, dst_addr);
+
+        // The trailing fragment has nowhere to land now that the partial state was dropped, so
+        // it's silently discarded rather than forwarded as a truncated PDU.
+        client.handle_eapol_msdu(src_addr, dst_addr, 1, false, &[0xBB, 0xCC, 0xDD]);
+        fake_device
+            .next_mlme_msg::<fidl_mlme::EapolIndication>()
+            .expect_err("expected empty channel");
+    }
+
     #[test]
     fn send_eapol_ind_too_large() {
         let mut fake_device = FakeDevice::new();
@@ -741,11 +2148,12 @@ mod tests {
         let mut client =
             make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
         client
-            .send_eapol_indication([1; 6], [2; 6], &[5; 256])
+            .send_eapol_indication([1; 6], [2; 6], &vec![5; DEFAULT_MAX_EAPOL_PDU_LEN + 1])
             .expect_err("sending too large EAPOL frame should fail");
         fake_device
             .next_mlme_msg::<fidl_mlme::EapolIndication>()
             .expect_err("expected empty channel");
+        assert_eq!(client.frame_drop_counts().eapol_pdu_too_large, 1);
     }
 
     #[test]
@@ -827,6 +2235,151 @@ mod tests {
         assert!(fake_device.wlan_queue.is_empty());
     }
 
+    #[test]
+    fn enter_and_exit_doze_sends_pm_bit() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+
+        client.set_power_management_mode(PowerManagementMode::PowerSaveMode);
+        assert!(!client.is_dozing());
+
+        client.enter_doze().expect("error entering doze");
+        assert!(client.is_dozing());
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+        assert_eq!(fake_device.wlan_queue[0].0[1] & 0b0001_0000, 0b0001_0000);
+
+        client.exit_doze().expect("error exiting doze");
+        assert!(!client.is_dozing());
+        assert_eq!(fake_device.wlan_queue.len(), 2);
+        assert_eq!(fake_device.wlan_queue[1].0[1] & 0b0001_0000, 0);
+    }
+
+    #[test]
+    fn eapol_frame_wakes_from_doze() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client.set_power_management_mode(PowerManagementMode::PowerSaveMode);
+        client.enter_doze().expect("error entering doze");
+
+        client.send_eapol_frame(IFACE_MAC, BSSID.0, false, &[5; 8]);
+        assert!(!client.is_dozing());
+    }
+
+    #[test]
+    fn connection_monitor_sends_probe_then_deauths_on_beacon_loss() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client.start_connection_monitoring(100 /* TU */);
+
+        // `signal_report_beacon_timeout` is 3 in `test_client_config`: the first two ticks are
+        // silently counted, the third sends the keep-alive probe.
+        client.handle_connection_monitor_tick();
+        client.handle_connection_monitor_tick();
+        assert_eq!(fake_device.wlan_queue.len(), 0);
+        client.handle_connection_monitor_tick();
+        assert_eq!(fake_device.wlan_queue.len(), 1, "expected a keep-alive probe to be sent");
+
+        // No response arrives: the next tick (the probe timeout) should tear the connection down.
+        client.handle_connection_monitor_tick();
+        assert_eq!(fake_device.wlan_queue.len(), 2, "expected a deauth frame to be sent");
+    }
+
+    #[test]
+    fn bssid_frame_resets_connection_monitor() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client.start_connection_monitoring(100 /* TU */);
+
+        client.handle_connection_monitor_tick();
+        client.handle_connection_monitor_tick();
+        client.on_bssid_frame_seen();
+        assert_eq!(client.connection_monitor.missed_beacon_count, 0);
+    }
+
+    #[test]
+    fn frame_transmitter_addr_reads_addr2() {
+        #[rustfmt::skip]
+        let frame = [
+            0b1000_00_00, 0, // FC: beacon
+            0, 0, // Duration
+            6, 6, 6, 6, 6, 6, // addr1
+            7, 7, 7, 7, 7, 7, // addr2 (transmitter)
+            6, 6, 6, 6, 6, 6, // addr3
+        ];
+        assert_eq!(Some([7u8; 6]), frame_transmitter_addr(&&frame[..]));
+
+        // Too short to contain Address 2.
+        assert_eq!(None, frame_transmitter_addr(&&frame[..12]));
+    }
+
+    #[test]
+    fn frame_transmitter_addr_distinguishes_bssid_from_other_devices() {
+        #[rustfmt::skip]
+        let from_another_device = [
+            0b1000_00_00, 0, // FC: beacon
+            0, 0, // Duration
+            6, 6, 6, 6, 6, 6, // addr1
+            9, 9, 9, 9, 9, 9, // addr2 (transmitter): not our BSSID
+            6, 6, 6, 6, 6, 6, // addr3
+        ];
+        assert_ne!(frame_transmitter_addr(&&from_another_device[..]), Some(BSSID.0));
+    }
+
+    #[test]
+    fn pmf_drops_unprotected_deauth() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+
+        // PMF not negotiated yet: unprotected deauth is honored.
+        assert!(!client.should_drop_unprotected_mgmt_frame(false));
+
+        client.pmf_enabled = true;
+        assert!(client.should_drop_unprotected_mgmt_frame(false));
+        assert!(!client.should_drop_unprotected_mgmt_frame(true));
+    }
+
+    #[test]
+    fn sa_query_responder_echoes_transaction_id() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client.handle_sa_query_request(0x1234).expect("error sending SA Query response");
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+    }
+
+    #[test]
+    fn sa_query_initiator_gives_up_after_retries() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client.start_sa_query().expect("error starting SA Query");
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+
+        // Each of the first `SA_QUERY_MAX_RETRIES` timeouts retransmits the request; the timeout
+        // after the last retransmission gives up.
+        for _ in 0..=SA_QUERY_MAX_RETRIES {
+            client.handle_sa_query_retry();
+        }
+        assert!(client.sa_query.is_none(), "SA Query should have given up after max retries");
+
+        let deauth_ind = fake_device
+            .next_mlme_msg::<fidl_mlme::DeauthenticateIndication>()
+            .expect("error reading DEAUTHENTICATE.indication");
+        assert_eq!(deauth_ind.peer_sta_address, BSSID.0);
+    }
+
     #[test]
     fn send_ps_poll_frame() {
         let mut fake_device = FakeDevice::new();
@@ -835,4 +2388,73 @@ mod tests {
             make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
         client.send_ps_poll_frame(0xABCD).expect("failed sending PS POLL frame");
     }
+
+    fn make_tim(bmp_ctrl: u8, bitmap: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0u8 /* dtim_count */, 0u8 /* dtim_period */, bmp_ctrl];
+        bytes.extend_from_slice(bitmap);
+        bytes
+    }
+
+    #[test]
+    fn ps_poll_issued_when_tim_bit_set_for_our_aid() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client.enter_doze().expect("error entering doze");
+        fake_device.wlan_queue.clear();
+
+        // Bitmap Control: offset 0. Bitmap octet 0, bit 1 set: AID 1 has buffered traffic.
+        let tim_bytes = make_tim(0b0000_0000, &[0b0000_0010]);
+        let (header, bitmap) =
+            zerocopy::LayoutVerified::<&[u8], wlan_common::ie::TimHeader>::new_from_prefix(
+                &tim_bytes[..],
+            )
+            .expect("error parsing TIM header");
+        let tim = wlan_common::ie::TimView { header, bitmap };
+
+        client.handle_beacon_tim(1, &tim);
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+    }
+
+    #[test]
+    fn ps_poll_not_issued_when_tim_bit_clear_for_our_aid() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client.enter_doze().expect("error entering doze");
+        fake_device.wlan_queue.clear();
+
+        // Bitmap Control: offset 0. Bitmap octet 0, bit 1 clear: AID 1 has nothing buffered.
+        let tim_bytes = make_tim(0b0000_0000, &[0b0000_0000]);
+        let (header, bitmap) =
+            zerocopy::LayoutVerified::<&[u8], wlan_common::ie::TimHeader>::new_from_prefix(
+                &tim_bytes[..],
+            )
+            .expect("error parsing TIM header");
+        let tim = wlan_common::ie::TimView { header, bitmap };
+
+        client.handle_beacon_tim(1, &tim);
+        assert_eq!(fake_device.wlan_queue.len(), 0);
+    }
+
+    #[test]
+    fn ps_poll_keeps_polling_while_more_data_is_set() {
+        let mut fake_device = FakeDevice::new();
+        let mut fake_scheduler = FakeScheduler::new();
+        let mut client =
+            make_client_station(fake_device.as_device(), fake_scheduler.as_scheduler());
+        client.enter_doze().expect("error entering doze");
+        fake_device.wlan_queue.clear();
+
+        client.ps_poll = Some(PsPollState { aid: 1, retries_remaining: PS_POLL_MAX_RETRIES });
+        client.handle_ps_poll_progress(true /* more_data */);
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+        assert!(client.ps_poll.is_some());
+
+        client.handle_ps_poll_progress(false /* more_data */);
+        assert_eq!(fake_device.wlan_queue.len(), 1);
+        assert!(client.ps_poll.is_none());
+    }
 }